@@ -0,0 +1,262 @@
+use anchor_client::anchor_lang::AccountDeserialize;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Maximum number of accounts per getMultipleAccounts request
+pub const MAX_ACCOUNTS_PER_RPC_REQUEST: usize = 100;
+
+/// Source of raw account data. Result is in the same order as the requested pubkeys, None for missing accounts.
+#[async_trait]
+pub trait AccountProvider: Sync {
+    async fn get_accounts_data(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>>;
+
+    async fn get_account_data(&self, pubkey: &Pubkey) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .get_accounts_data(std::slice::from_ref(pubkey))
+            .await?
+            .pop()
+            .flatten())
+    }
+}
+
+/// Deserialize an anchor account from raw account data. Zero copy accounts require the data after the discriminator to be aligned for u128, so the data is copied into an aligned buffer first.
+pub fn deserialize_anchor_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let discriminator_len = 8;
+    let mut buffer = vec![0u128; (data.len() + discriminator_len) / 16 + 1];
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+    // Account data start at byte 8 of the buffer, so that the data after the discriminator is 16 bytes aligned
+    let aligned_data = &mut bytes[discriminator_len..discriminator_len + data.len()];
+    aligned_data.copy_from_slice(data);
+
+    Ok(T::try_deserialize(&mut &aligned_data[..])?)
+}
+
+/// Fetch and deserialize an anchor account. None if the account doesn't exist.
+pub async fn get_anchor_account<T: AccountDeserialize>(
+    provider: &impl AccountProvider,
+    pubkey: &Pubkey,
+) -> Result<Option<T>> {
+    provider
+        .get_account_data(pubkey)
+        .await?
+        .map(|data| deserialize_anchor_account(&data))
+        .transpose()
+        .with_context(|| format!("Failed to deserialize account {}", pubkey))
+}
+
+/// Fetch and deserialize multiple anchor accounts. Missing accounts are skipped.
+pub async fn get_anchor_accounts<T: AccountDeserialize>(
+    provider: &impl AccountProvider,
+    pubkeys: &[Pubkey],
+) -> Result<HashMap<Pubkey, T>> {
+    let mut accounts = HashMap::new();
+
+    for (pubkey, data) in pubkeys
+        .iter()
+        .zip(provider.get_accounts_data(pubkeys).await?)
+    {
+        if let Some(data) = data {
+            let account = deserialize_anchor_account(&data)
+                .with_context(|| format!("Failed to deserialize account {}", pubkey))?;
+            accounts.insert(*pubkey, account);
+        }
+    }
+
+    Ok(accounts)
+}
+
+/// Fetch accounts from RPC, batched by getMultipleAccounts.
+pub struct RpcAccountProvider {
+    rpc_client: RpcClient,
+}
+
+impl RpcAccountProvider {
+    pub fn new(rpc_client: RpcClient) -> Self {
+        Self { rpc_client }
+    }
+}
+
+#[async_trait]
+impl AccountProvider for RpcAccountProvider {
+    async fn get_accounts_data(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut accounts_data = Vec::with_capacity(pubkeys.len());
+
+        for chunk in pubkeys.chunks(MAX_ACCOUNTS_PER_RPC_REQUEST) {
+            let accounts = self.rpc_client.get_multiple_accounts(chunk).await?;
+            accounts_data.extend(accounts.into_iter().map(|account| Some(account?.data)));
+        }
+
+        Ok(accounts_data)
+    }
+}
+
+/// Accounts kept in memory. Useful for unit tests, or to hold accounts streamed from elsewhere.
+#[derive(Default, Clone)]
+pub struct InMemoryAccountProvider {
+    accounts: HashMap<Pubkey, Vec<u8>>,
+}
+
+impl InMemoryAccountProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, pubkey: Pubkey, data: Vec<u8>) {
+        self.accounts.insert(pubkey, data);
+    }
+
+    pub fn remove(&mut self, pubkey: &Pubkey) -> Option<Vec<u8>> {
+        self.accounts.remove(pubkey)
+    }
+
+    /// Copy accounts from another provider. Missing accounts are skipped.
+    pub async fn load_from(
+        &mut self,
+        provider: &impl AccountProvider,
+        pubkeys: &[Pubkey],
+    ) -> Result<()> {
+        for (pubkey, data) in pubkeys
+            .iter()
+            .zip(provider.get_accounts_data(pubkeys).await?)
+        {
+            if let Some(data) = data {
+                self.insert(*pubkey, data);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AccountProvider for InMemoryAccountProvider {
+    async fn get_accounts_data(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.accounts.get(pubkey).cloned())
+            .collect())
+    }
+}
+
+/// Accounts stored on disk as raw account data, one .bin file per account. Same format as the fixtures in programs/lb_clmm/tests/fixtures.
+/// The file is named after the account pubkey, unless a file name was assigned through with_file_name.
+pub struct SnapshotAccountProvider {
+    directory: PathBuf,
+    file_names: HashMap<Pubkey, String>,
+}
+
+impl SnapshotAccountProvider {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_names: HashMap::new(),
+        }
+    }
+
+    /// Assign a file name to the account. Eg: lb_pair.bin
+    pub fn with_file_name(mut self, pubkey: Pubkey, file_name: impl Into<String>) -> Self {
+        self.file_names.insert(pubkey, file_name.into());
+        self
+    }
+
+    fn account_path(&self, pubkey: &Pubkey) -> PathBuf {
+        match self.file_names.get(pubkey) {
+            Some(file_name) => self.directory.join(file_name),
+            None => self.directory.join(format!("{}.bin", pubkey)),
+        }
+    }
+
+    /// Write account data to the snapshot
+    pub fn write_account_data(&self, pubkey: &Pubkey, data: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        std::fs::write(self.account_path(pubkey), data)?;
+        Ok(())
+    }
+
+    /// Save accounts from another provider into the snapshot. Missing accounts are skipped.
+    pub async fn save_from(
+        &self,
+        provider: &impl AccountProvider,
+        pubkeys: &[Pubkey],
+    ) -> Result<()> {
+        for (pubkey, data) in pubkeys
+            .iter()
+            .zip(provider.get_accounts_data(pubkeys).await?)
+        {
+            if let Some(data) = data {
+                self.write_account_data(pubkey, &data)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AccountProvider for SnapshotAccountProvider {
+    async fn get_accounts_data(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        pubkeys
+            .iter()
+            .map(|pubkey| {
+                let path = self.account_path(pubkey);
+                if path.exists() {
+                    Ok(Some(std::fs::read(&path).with_context(|| {
+                        format!("Failed to read {}", path.display())
+                    })?))
+                } else {
+                    Ok(None)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Snapshot of the pair in programs/lb_clmm/tests/fixtures
+#[cfg(test)]
+pub(crate) fn fixture_provider() -> (Pubkey, SnapshotAccountProvider) {
+    use std::str::FromStr;
+
+    let lb_pair = Pubkey::from_str("EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig").unwrap();
+    let bin_array_1 = Pubkey::from_str("5Sm2ecMeqohRkNpFJPWSqHL1BkA7AEW4ck8TmdF1gD4t").unwrap();
+    let bin_array_2 = Pubkey::from_str("E6gur9Jw8675DCR7GpJVhoSrkruRgt8EdEVqLAc5RLUt").unwrap();
+
+    let provider = SnapshotAccountProvider::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../programs/lb_clmm/tests/fixtures/EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig"
+    ))
+    .with_file_name(lb_pair, "lb_pair.bin")
+    .with_file_name(bin_array_1, "bin_array_1.bin")
+    .with_file_name(bin_array_2, "bin_array_2.bin");
+
+    (lb_pair, provider)
+}
+
+/// Pair of the snapshot, and the bin arrays around the active bin. Missing bin arrays are skipped.
+#[cfg(test)]
+pub(crate) async fn fixture_pair() -> (
+    Pubkey,
+    lb_clmm::state::lb_pair::LbPair,
+    HashMap<Pubkey, lb_clmm::state::bin::BinArray>,
+) {
+    use crate::quote::get_bin_array_pubkeys_for_swap;
+
+    let (lb_pair_pubkey, provider) = fixture_provider();
+    let lb_pair: lb_clmm::state::lb_pair::LbPair = get_anchor_account(&provider, &lb_pair_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut bin_array_pubkeys =
+        get_bin_array_pubkeys_for_swap(lb_pair_pubkey, &lb_pair, None, true, 3).unwrap();
+    bin_array_pubkeys
+        .extend(get_bin_array_pubkeys_for_swap(lb_pair_pubkey, &lb_pair, None, false, 3).unwrap());
+    let bin_arrays = get_anchor_accounts(&provider, &bin_array_pubkeys)
+        .await
+        .unwrap();
+
+    (lb_pair_pubkey, lb_pair, bin_arrays)
+}
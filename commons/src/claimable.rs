@@ -0,0 +1,175 @@
+use crate::position::get_bin;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use lb_clmm::{
+    constants::NUM_REWARDS,
+    math::u64x64_math::SCALE_OFFSET,
+    state::{
+        bin::{Bin, BinArray},
+        lb_pair::LbPair,
+        position::PositionV2,
+    },
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct ClaimableFeeAndReward {
+    /// Claimable swap fee in token X
+    pub fee_x: u64,
+    /// Claimable swap fee in token Y
+    pub fee_y: u64,
+    /// Claimable farming rewards, in the reward token
+    pub rewards: [u64; NUM_REWARDS],
+}
+
+/// Reward per token stored of the active bin, accrued up to current_timestamp. Same as the on-chain BinArray::update_all_rewards, which only happen on the next swap, or liquidity change of the active bin.
+pub fn get_reward_per_token_stored_at(
+    lb_pair: &LbPair,
+    active_bin: &Bin,
+    current_timestamp: u64,
+) -> Result<[u128; NUM_REWARDS]> {
+    let mut reward_per_token_stored = active_bin.reward_per_token_stored;
+
+    // To make it simple the program truncate decimals of liquidity_supply for the calculation
+    let liquidity_supply: u64 = (active_bin.liquidity_supply >> SCALE_OFFSET)
+        .try_into()
+        .context("TypeCastFailed")?;
+    if liquidity_supply == 0 {
+        return Ok(reward_per_token_stored);
+    }
+
+    for (reward_idx, reward_info) in lb_pair.reward_infos.iter().enumerate() {
+        if !reward_info.initialized() {
+            continue;
+        }
+
+        // Skip stale timestamp, which the program would reject
+        let last_time_reward_applicable =
+            std::cmp::min(current_timestamp, reward_info.reward_duration_end);
+        if last_time_reward_applicable <= reward_info.last_update_time {
+            continue;
+        }
+
+        let reward_per_token_stored_delta = reward_info
+            .calculate_reward_per_token_stored_since_last_update(
+                current_timestamp,
+                liquidity_supply,
+            )?;
+
+        reward_per_token_stored[reward_idx] = reward_per_token_stored[reward_idx]
+            .checked_add(reward_per_token_stored_delta)
+            .context("MathOverflow")?;
+    }
+
+    Ok(reward_per_token_stored)
+}
+
+/// Exact swap fee and farming rewards claimable by the position at current_timestamp. Simulate the on-chain fee, and reward checkpoint on a copy of the position.
+pub fn get_claimable_fee_and_reward(
+    lb_pair: &LbPair,
+    position: &PositionV2,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    current_timestamp: u64,
+) -> Result<ClaimableFeeAndReward> {
+    let mut position = *position;
+
+    for bin_id in position.lower_bin_id..=position.upper_bin_id {
+        // Bin without liquidity share doesn't earn
+        if position.get_liquidity_share_in_bin(bin_id)? == 0 {
+            continue;
+        }
+
+        let mut bin = get_bin(bin_arrays, bin_id)?;
+        if bin_id == lb_pair.active_id {
+            bin.reward_per_token_stored =
+                get_reward_per_token_stored_at(lb_pair, &bin, current_timestamp)?;
+        }
+
+        position.update_reward_per_token_stored(bin_id, &bin)?;
+        position.update_fee_per_token_stored(bin_id, &bin)?;
+    }
+
+    let mut claimable = ClaimableFeeAndReward::default();
+
+    for fee_info in position.fee_infos.iter() {
+        claimable.fee_x = claimable
+            .fee_x
+            .checked_add(fee_info.fee_x_pending)
+            .context("MathOverflow")?;
+        claimable.fee_y = claimable
+            .fee_y
+            .checked_add(fee_info.fee_y_pending)
+            .context("MathOverflow")?;
+    }
+
+    for (reward_index, reward) in claimable.rewards.iter_mut().enumerate() {
+        *reward = position.get_total_reward(reward_index)?;
+    }
+
+    Ok(claimable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_provider::fixture_pair;
+    use lb_clmm::math::u128x128_math::Rounding;
+    use lb_clmm::math::utils_math::safe_mul_div_cast;
+    use lb_clmm::state::lb_pair::CustomU128;
+
+    #[tokio::test]
+    async fn test_get_claimable_fee_and_reward() {
+        let (lb_pair_pubkey, mut lb_pair, bin_arrays) = fixture_pair().await;
+
+        let active_bin = get_bin(&bin_arrays, lb_pair.active_id).unwrap();
+        let liquidity_supply = (active_bin.liquidity_supply >> SCALE_OFFSET) as u64;
+        assert!(liquidity_supply > 0);
+
+        // Farm distributing 1 token per second (Q64.64) for 100 seconds, last updated at 1000
+        let reward_info = &mut lb_pair.reward_infos[0];
+        reward_info.mint = Pubkey::new_unique();
+        reward_info.reward_rate = CustomU128::from_u128(1u128 << SCALE_OFFSET);
+        reward_info.last_update_time = 1000;
+        reward_info.reward_duration_end = 1100;
+
+        // Position owning all liquidity of the active bin
+        let mut position: PositionV2 = bytemuck::Zeroable::zeroed();
+        position.lb_pair = lb_pair_pubkey;
+        position.lower_bin_id = lb_pair.active_id;
+        position.upper_bin_id = lb_pair.active_id;
+        position.liquidity_shares[0] = active_bin.liquidity_supply;
+        position.fee_infos[0].fee_x_per_token_complete = active_bin.fee_amount_x_per_token_stored;
+        position.fee_infos[0].fee_y_per_token_complete = active_bin.fee_amount_y_per_token_stored;
+        position.fee_infos[0].fee_y_pending = 50;
+        position.reward_infos[0].reward_per_token_completes = active_bin.reward_per_token_stored;
+        position.reward_infos[0].reward_pendings[0] = 7;
+
+        // Nothing accrued before the last update
+        let claimable =
+            get_claimable_fee_and_reward(&lb_pair, &position, &bin_arrays, 900).unwrap();
+        assert_eq!(claimable.fee_x, 0);
+        assert_eq!(claimable.fee_y, 50);
+        assert_eq!(claimable.rewards, [7, 0]);
+
+        // Reward stop accruing after the reward duration end
+        for current_timestamp in [1050, 1100, 2000] {
+            let elapsed = std::cmp::min(current_timestamp, 1100) - 1000;
+            let reward_per_token_stored_delta: u128 = safe_mul_div_cast(
+                elapsed.into(),
+                1u128 << SCALE_OFFSET,
+                liquidity_supply.into(),
+                Rounding::Down,
+            )
+            .unwrap();
+            let expected_reward =
+                ((liquidity_supply as u128 * reward_per_token_stored_delta) >> SCALE_OFFSET) as u64;
+
+            let claimable =
+                get_claimable_fee_and_reward(&lb_pair, &position, &bin_arrays, current_timestamp)
+                    .unwrap();
+            assert_eq!(claimable.rewards[0], 7 + expected_reward);
+            assert!(claimable.rewards[0] <= 7 + elapsed);
+            assert_eq!(claimable.rewards[1], 0);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use lb_clmm::{
+    math::{price_math::get_price_from_id, u64x64_math::SCALE_OFFSET},
+    state::{bin::BinArray, lb_pair::LbPair},
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct OrderbookLevel {
+    pub bin_id: i32,
+    /// Price of token X in token Y, per token
+    pub price: f64,
+    /// Size of the level in token X, per token. Bid size is the token Y reserve converted at the bin price.
+    pub size: f64,
+    /// Size from the best level up to, and including this level
+    pub cumulative_size: f64,
+    /// Reserve of the bin backing the level. Token X for ask, token Y for bid.
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Orderbook {
+    pub active_id: i32,
+    /// Price of the active bin, per token
+    pub mid_price: f64,
+    /// Token Y reserves, sorted from the highest price
+    pub bids: Vec<OrderbookLevel>,
+    /// Token X reserves, sorted from the lowest price
+    pub asks: Vec<OrderbookLevel>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DepthWithin {
+    /// Bid size in token X, per token
+    pub bid_size: f64,
+    /// Bid size valued in token Y, per token
+    pub bid_value: f64,
+    /// Ask size in token X, per token
+    pub ask_size: f64,
+    /// Ask size valued in token Y, per token
+    pub ask_value: f64,
+}
+
+/// Price of token X in token Y, per token. Q64.64 price is per lamport.
+fn get_price_per_token(bin_id: i32, bin_step: u16, decimals: (u8, u8)) -> Result<f64> {
+    let price = get_price_from_id(bin_id, bin_step)?;
+    let price_per_lamport = price as f64 / 2f64.powi(SCALE_OFFSET.into());
+    let (token_x_decimals, token_y_decimals) = decimals;
+
+    Ok(price_per_lamport * 10f64.powi(i32::from(token_x_decimals) - i32::from(token_y_decimals)))
+}
+
+fn accumulate(levels: &mut [OrderbookLevel]) {
+    let mut cumulative_size = 0.0;
+    for level in levels.iter_mut() {
+        cumulative_size += level.size;
+        level.cumulative_size = cumulative_size;
+    }
+}
+
+/// Build bid, and ask levels from the reserves of the bins. Decimals is (token X decimals, token Y decimals).
+/// The active bin can hold both tokens, and show up on both sides.
+pub fn build_orderbook(
+    lb_pair: &LbPair,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    decimals: (u8, u8),
+) -> Result<Orderbook> {
+    let (token_x_decimals, token_y_decimals) = decimals;
+    let one_token_x = 10f64.powi(token_x_decimals.into());
+    let one_token_y = 10f64.powi(token_y_decimals.into());
+
+    let mut orderbook = Orderbook {
+        active_id: lb_pair.active_id,
+        mid_price: get_price_per_token(lb_pair.active_id, lb_pair.bin_step, decimals)?,
+        ..Default::default()
+    };
+
+    for bin_array in bin_arrays.values() {
+        let (lower_bin_id, _) = BinArray::get_bin_array_lower_upper_bin_id(bin_array.index as i32)?;
+
+        for (idx, bin) in bin_array.bins.iter().enumerate() {
+            if bin.amount_x == 0 && bin.amount_y == 0 {
+                continue;
+            }
+
+            let bin_id = lower_bin_id
+                .checked_add(idx as i32)
+                .context("MathOverflow")?;
+            let price = get_price_per_token(bin_id, lb_pair.bin_step, decimals)?;
+
+            if bin.amount_x > 0 {
+                orderbook.asks.push(OrderbookLevel {
+                    bin_id,
+                    price,
+                    size: bin.amount_x as f64 / one_token_x,
+                    cumulative_size: 0.0,
+                    amount: bin.amount_x,
+                });
+            }
+
+            if bin.amount_y > 0 {
+                orderbook.bids.push(OrderbookLevel {
+                    bin_id,
+                    price,
+                    size: bin.amount_y as f64 / one_token_y / price,
+                    cumulative_size: 0.0,
+                    amount: bin.amount_y,
+                });
+            }
+        }
+    }
+
+    orderbook.asks.sort_by_key(|level| level.bin_id);
+    orderbook
+        .bids
+        .sort_by_key(|level| std::cmp::Reverse(level.bin_id));
+
+    accumulate(&mut orderbook.asks);
+    accumulate(&mut orderbook.bids);
+
+    Ok(orderbook)
+}
+
+impl Orderbook {
+    /// Liquidity priced within ±percentage of the mid price. Eg: 2.0 for ±2%
+    pub fn liquidity_within(&self, percentage: f64) -> DepthWithin {
+        let min_price = self.mid_price * (1.0 - percentage / 100.0);
+        let max_price = self.mid_price * (1.0 + percentage / 100.0);
+
+        let mut depth = DepthWithin::default();
+
+        for level in self
+            .bids
+            .iter()
+            .take_while(|level| level.price >= min_price)
+        {
+            depth.bid_size += level.size;
+            depth.bid_value += level.size * level.price;
+        }
+
+        for level in self
+            .asks
+            .iter()
+            .take_while(|level| level.price <= max_price)
+        {
+            depth.ask_size += level.size;
+            depth.ask_value += level.size * level.price;
+        }
+
+        depth
+    }
+
+    /// Amount in, per token and excluding swap fee, to move the price by percentage from the mid price. Positive percentage buys token X with token Y, and return the token Y required.
+    /// Negative percentage sells token X, and return the token X required. Only levels priced before the target are consumed.
+    pub fn cost_to_move_price(&self, percentage: f64) -> f64 {
+        let target_price = self.mid_price * (1.0 + percentage / 100.0);
+
+        if percentage >= 0.0 {
+            self.asks
+                .iter()
+                .take_while(|level| level.price < target_price)
+                .map(|level| level.size * level.price)
+                .sum()
+        } else {
+            self.bids
+                .iter()
+                .take_while(|level| level.price > target_price)
+                .map(|level| level.size)
+                .sum()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_provider::fixture_pair;
+
+    #[tokio::test]
+    async fn test_build_orderbook() {
+        let (_, lb_pair, bin_arrays) = fixture_pair().await;
+
+        // Per lamport
+        let orderbook = build_orderbook(&lb_pair, &bin_arrays, (0, 0)).unwrap();
+        assert!(!orderbook.asks.is_empty());
+        assert!(!orderbook.bids.is_empty());
+
+        assert!(orderbook
+            .asks
+            .windows(2)
+            .all(|levels| levels[0].price < levels[1].price));
+        assert!(orderbook
+            .bids
+            .windows(2)
+            .all(|levels| levels[0].price > levels[1].price));
+        assert!(orderbook.asks[0].price >= orderbook.mid_price);
+        assert!(orderbook.bids[0].price <= orderbook.mid_price);
+
+        let total_amount_x: u64 = orderbook.asks.iter().map(|level| level.amount).sum();
+        let total_ask_size = orderbook.asks.last().unwrap().cumulative_size;
+        assert_eq!(total_ask_size, total_amount_x as f64);
+
+        let depth = orderbook.liquidity_within(f64::INFINITY);
+        assert_eq!(depth.ask_size, total_ask_size);
+        assert_eq!(
+            depth.bid_size,
+            orderbook.bids.last().unwrap().cumulative_size
+        );
+
+        let depth_1 = orderbook.liquidity_within(1.0);
+        assert!(depth_1.ask_size <= depth.ask_size && depth_1.bid_size <= depth.bid_size);
+
+        assert_eq!(orderbook.cost_to_move_price(0.0), 0.0);
+        assert!(orderbook.cost_to_move_price(1.0) <= orderbook.cost_to_move_price(5.0));
+        assert!(orderbook.cost_to_move_price(-1.0) <= orderbook.cost_to_move_price(-5.0));
+
+        // Per token price scale by 10 ** (decimals x - decimals y)
+        let orderbook_in_token = build_orderbook(&lb_pair, &bin_arrays, (9, 6)).unwrap();
+        let ratio = orderbook_in_token.mid_price / orderbook.mid_price;
+        assert!((ratio - 1000.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::{Context, Result};
+use lb_clmm::state::{lb_pair::LbPair, preset_parameters::PresetParameter};
+
+#[derive(Debug, Clone, Copy)]
+pub struct SimulatedSwap {
+    /// Unix timestamp of the swap
+    pub timestamp: i64,
+    /// Number of bins crossed by the swap. Positive swap token Y for X, and move the price up. Negative move the price down.
+    pub bins_crossed: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeeForecastStep {
+    pub timestamp: i64,
+    pub start_active_id: i32,
+    pub end_active_id: i32,
+    /// Volatility reference after update_references
+    pub volatility_reference: u32,
+    /// Volatility accumulator of the last bin of the swap
+    pub volatility_accumulator: u32,
+    /// Total fee rate of the first bin of the swap. This is in 1e9 unit.
+    pub start_fee_rate: u128,
+    /// Total fee rate of the last bin of the swap. This is in 1e9 unit.
+    pub end_fee_rate: u128,
+    /// Total fee rate averaged over the bins of the swap, assuming equal volume per bin. This is in 1e9 unit.
+    pub average_fee_rate: u128,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeeDecay {
+    /// Fee rate of the next swap within filter_period. High frequency trade keep the accumulated volatility.
+    pub within_filter_period: u128,
+    /// Fee rate of the next swap from filter_period until decay_period. The volatility is reduced by reduction_factor.
+    pub within_decay_period: u128,
+    /// Fee rate of the next swap from decay_period onward. The volatility is reset, only base fee is charged.
+    pub after_decay_period: u128,
+}
+
+/// Pair to simulate the fee of a preset parameter before the pair is created
+pub fn lb_pair_from_preset(preset_parameter: &PresetParameter, active_id: i32) -> LbPair {
+    let mut lb_pair: LbPair = bytemuck::Zeroable::zeroed();
+    lb_pair.parameters = preset_parameter.to_static_parameters();
+    lb_pair.bin_step = preset_parameter.bin_step;
+    lb_pair.active_id = active_id;
+    lb_pair.v_parameters.index_reference = active_id;
+    lb_pair
+}
+
+/// Replay the swaps through the volatility accumulator the same way the program does, and report the fee rate of each swap.
+/// Volatility surcharge is excluded as it depends on the swap ordering within a slot.
+pub fn forecast_fees(lb_pair: &LbPair, swaps: &[SimulatedSwap]) -> Result<Vec<FeeForecastStep>> {
+    let mut lb_pair = *lb_pair;
+
+    let mut steps = Vec::with_capacity(swaps.len());
+
+    for swap in swaps {
+        anyhow::ensure!(
+            swap.timestamp >= lb_pair.v_parameters.last_update_timestamp,
+            "Swaps must be sorted by timestamp"
+        );
+
+        let start_active_id = lb_pair.active_id;
+        lb_pair.update_references(swap.timestamp)?;
+        let volatility_reference = lb_pair.v_parameters.volatility_reference;
+
+        let swap_for_y = swap.bins_crossed < 0;
+        let bins_crossed = swap.bins_crossed.unsigned_abs();
+
+        let mut start_fee_rate = 0;
+        let mut end_fee_rate = 0;
+        let mut total_fee_rate = 0u128;
+
+        for i in 0..=bins_crossed {
+            lb_pair.update_volatility_accumulator()?;
+            end_fee_rate = lb_pair.get_total_fee()?;
+            if i == 0 {
+                start_fee_rate = end_fee_rate;
+            }
+            total_fee_rate = total_fee_rate
+                .checked_add(end_fee_rate)
+                .context("MathOverflow")?;
+
+            if i < bins_crossed {
+                lb_pair.advance_active_bin(swap_for_y)?;
+            }
+        }
+
+        lb_pair.v_parameters.last_update_timestamp = swap.timestamp;
+
+        steps.push(FeeForecastStep {
+            timestamp: swap.timestamp,
+            start_active_id,
+            end_active_id: lb_pair.active_id,
+            volatility_reference,
+            volatility_accumulator: lb_pair.v_parameters.volatility_accumulator,
+            start_fee_rate,
+            end_fee_rate,
+            average_fee_rate: total_fee_rate / (u128::from(bins_crossed) + 1),
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Fee rate of the next swap in the active bin, if it happen elapsed seconds after the last swap
+pub fn get_fee_rate_after(lb_pair: &LbPair, elapsed: i64) -> Result<u128> {
+    let mut lb_pair = *lb_pair;
+
+    let current_timestamp = lb_pair
+        .v_parameters
+        .last_update_timestamp
+        .checked_add(elapsed)
+        .context("MathOverflow")?;
+    lb_pair.update_volatility_parameters(current_timestamp)?;
+
+    Ok(lb_pair.get_total_fee()?)
+}
+
+/// How the fee rate of the next swap decay over filter_period, and decay_period since the last swap
+pub fn get_fee_decay(lb_pair: &LbPair) -> Result<FeeDecay> {
+    Ok(FeeDecay {
+        within_filter_period: get_fee_rate_after(lb_pair, 0)?,
+        within_decay_period: get_fee_rate_after(
+            lb_pair,
+            lb_pair.parameters.get_filter_period().into(),
+        )?,
+        after_decay_period: get_fee_rate_after(
+            lb_pair,
+            lb_pair.parameters.get_decay_period().into(),
+        )?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset_parameter() -> PresetParameter {
+        PresetParameter {
+            bin_step: 10,
+            base_factor: 10000,
+            filter_period: 30,
+            decay_period: 600,
+            reduction_factor: 5000,
+            variable_fee_control: 40000,
+            max_volatility_accumulator: 350000,
+            min_bin_id: -443636,
+            max_bin_id: 443636,
+            protocol_share: 0,
+            padding: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_forecast_fees() {
+        let lb_pair = lb_pair_from_preset(&preset_parameter(), 0);
+        let base_fee_rate = lb_pair.get_base_fee().unwrap();
+
+        let start_timestamp = 1_700_000_000;
+        let swaps = [
+            SimulatedSwap {
+                timestamp: start_timestamp,
+                bins_crossed: 5,
+            },
+            // High frequency trade within filter period
+            SimulatedSwap {
+                timestamp: start_timestamp + 10,
+                bins_crossed: 5,
+            },
+            // Within decay period
+            SimulatedSwap {
+                timestamp: start_timestamp + 100,
+                bins_crossed: -3,
+            },
+            // Out of decay period
+            SimulatedSwap {
+                timestamp: start_timestamp + 1000,
+                bins_crossed: 0,
+            },
+        ];
+
+        let steps = forecast_fees(&lb_pair, &swaps).unwrap();
+        assert_eq!(steps.len(), 4);
+
+        assert_eq!(steps[0].start_fee_rate, base_fee_rate);
+        assert_eq!(steps[0].end_active_id, 5);
+        assert_eq!(steps[0].volatility_accumulator, 50000);
+        assert!(steps[0].end_fee_rate > steps[0].average_fee_rate);
+        assert!(steps[0].average_fee_rate > steps[0].start_fee_rate);
+
+        // Index reference is kept, the volatility keep accumulating
+        assert_eq!(steps[1].start_fee_rate, steps[0].end_fee_rate);
+        assert_eq!(steps[1].volatility_accumulator, 100000);
+
+        // Reduction factor of 50%
+        assert_eq!(steps[2].volatility_reference, 50000);
+        assert_eq!(steps[2].end_active_id, 7);
+        assert_eq!(steps[2].volatility_accumulator, 80000);
+
+        assert_eq!(steps[3].volatility_reference, 0);
+        assert_eq!(steps[3].start_fee_rate, base_fee_rate);
+        assert_eq!(steps[3].end_fee_rate, base_fee_rate);
+
+        // Unsorted swaps
+        assert!(forecast_fees(&lb_pair, &[swaps[1], swaps[0]]).is_err());
+    }
+
+    #[test]
+    fn test_get_fee_decay() {
+        let mut lb_pair = lb_pair_from_preset(&preset_parameter(), 0);
+        let base_fee_rate = lb_pair.get_base_fee().unwrap();
+
+        // Last swap crossed 10 bins
+        lb_pair.active_id = 10;
+        lb_pair.v_parameters.volatility_accumulator = 100000;
+        lb_pair.v_parameters.last_update_timestamp = 1_700_000_000;
+
+        let fee_decay = get_fee_decay(&lb_pair).unwrap();
+        assert_eq!(
+            fee_decay.within_filter_period,
+            base_fee_rate + lb_pair.compute_variable_fee(100000).unwrap()
+        );
+        assert_eq!(
+            fee_decay.within_decay_period,
+            base_fee_rate + lb_pair.compute_variable_fee(50000).unwrap()
+        );
+        assert_eq!(fee_decay.after_decay_period, base_fee_rate);
+    }
+}
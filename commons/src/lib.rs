@@ -1 +1,9 @@
+pub mod account_provider;
+pub mod claimable;
+pub mod depth;
+pub mod fee_forecast;
+pub mod liquidity;
+pub mod position;
 pub mod quote;
+pub mod quoter;
+pub mod route;
@@ -0,0 +1,205 @@
+use crate::position::get_bin_or_empty;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use lb_clmm::{
+    instructions::deposit::{
+        get_composition_fee, validate_add_liquidity_by_strategy_params,
+        LiquidityParameterByStrategy,
+    },
+    math::bin_math::get_liquidity,
+    state::{
+        bin::{get_liquidity_share, BinArray},
+        lb_pair::LbPair,
+    },
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct BinDepositQuote {
+    pub bin_id: i32,
+    /// Amount of token X deposited into the bin, excluding composition fee
+    pub amount_x: u64,
+    /// Amount of token Y deposited into the bin, excluding composition fee
+    pub amount_y: u64,
+    /// Liquidity share minted to the position
+    pub liquidity_share: u128,
+    /// Composition fee in token X. Only charged in the active bin.
+    pub composition_fee_x: u64,
+    /// Composition fee in token Y. Only charged in the active bin.
+    pub composition_fee_y: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AddLiquidityQuote {
+    pub bins: Vec<BinDepositQuote>,
+    /// Total amount of token X transferred from the user, including composition fee
+    pub total_amount_x: u64,
+    /// Total amount of token Y transferred from the user, including composition fee
+    pub total_amount_y: u64,
+    pub composition_fee_x: u64,
+    pub composition_fee_y: u64,
+    /// Portion of composition fee retained by the protocol
+    pub protocol_fee_x: u64,
+    pub protocol_fee_y: u64,
+    /// Amount of token X not distributed to any bin due to rounding
+    pub dust_x: u64,
+    /// Amount of token Y not distributed to any bin due to rounding
+    pub dust_y: u64,
+}
+
+/// Preview add liquidity by strategy. Bin arrays covering the strategy range should be provided, missing bin arrays are treated as empty.
+pub fn quote_add_liquidity_by_strategy(
+    lb_pair: &LbPair,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    liquidity_parameter: &LiquidityParameterByStrategy,
+    current_timestamp: u64,
+) -> Result<AddLiquidityQuote> {
+    validate_add_liquidity_by_strategy_params(
+        liquidity_parameter.active_id,
+        lb_pair.active_id,
+        liquidity_parameter.max_active_bin_slippage,
+        &liquidity_parameter.strategy_parameters,
+    )?;
+
+    let mut lb_pair = *lb_pair;
+    lb_pair.update_volatility_parameters(current_timestamp as i64)?;
+
+    let active_bin = get_bin_or_empty(bin_arrays, lb_pair.active_id)?;
+    let amounts_into_bin = liquidity_parameter.to_amounts_into_bin(
+        lb_pair.active_id,
+        lb_pair.bin_step,
+        active_bin.amount_x,
+        active_bin.amount_y,
+    )?;
+
+    let mut quote = AddLiquidityQuote::default();
+
+    for (bin_id, amount_x, amount_y) in amounts_into_bin {
+        if amount_x == 0 && amount_y == 0 {
+            continue;
+        }
+
+        let mut bin = get_bin_or_empty(bin_arrays, bin_id)?;
+        let price = bin.get_or_store_bin_price(bin_id, lb_pair.bin_step)?;
+
+        let in_liquidity = get_liquidity(amount_x, amount_y, price)?;
+        let bin_liquidity = get_liquidity(bin.amount_x, bin.amount_y, price)?;
+
+        let mut liquidity_share = if bin.is_zero_liquidity() {
+            in_liquidity
+        } else {
+            get_liquidity_share(in_liquidity, bin_liquidity, bin.liquidity_supply)?
+        };
+
+        let (mut composition_fee_x, mut composition_fee_y) = (0, 0);
+        let (mut amount_x_into_bin, mut amount_y_into_bin) = (amount_x, amount_y);
+
+        if bin_id == lb_pair.active_id {
+            (composition_fee_x, composition_fee_y) =
+                get_composition_fee(&lb_pair, &bin, liquidity_share, amount_x, amount_y)?;
+
+            if composition_fee_x > 0 || composition_fee_y > 0 {
+                amount_x_into_bin = amount_x
+                    .checked_sub(composition_fee_x)
+                    .context("MathOverflow")?;
+                amount_y_into_bin = amount_y
+                    .checked_sub(composition_fee_y)
+                    .context("MathOverflow")?;
+
+                let protocol_fee_x = lb_pair.compute_protocol_fee(composition_fee_x)?;
+                let protocol_fee_y = lb_pair.compute_protocol_fee(composition_fee_y)?;
+
+                bin.deposit_composition_fee(
+                    composition_fee_x - protocol_fee_x,
+                    composition_fee_y - protocol_fee_y,
+                )?;
+
+                quote.protocol_fee_x += protocol_fee_x;
+                quote.protocol_fee_y += protocol_fee_y;
+
+                let in_liquidity = get_liquidity(amount_x_into_bin, amount_y_into_bin, price)?;
+                let bin_liquidity = get_liquidity(bin.amount_x, bin.amount_y, price)?;
+                liquidity_share =
+                    get_liquidity_share(in_liquidity, bin_liquidity, bin.liquidity_supply)?;
+            }
+        }
+
+        quote.total_amount_x = quote
+            .total_amount_x
+            .checked_add(amount_x)
+            .context("MathOverflow")?;
+        quote.total_amount_y = quote
+            .total_amount_y
+            .checked_add(amount_y)
+            .context("MathOverflow")?;
+        quote.composition_fee_x += composition_fee_x;
+        quote.composition_fee_y += composition_fee_y;
+
+        quote.bins.push(BinDepositQuote {
+            bin_id,
+            amount_x: amount_x_into_bin,
+            amount_y: amount_y_into_bin,
+            liquidity_share,
+            composition_fee_x,
+            composition_fee_y,
+        });
+    }
+
+    quote.dust_x = liquidity_parameter
+        .amount_x
+        .saturating_sub(quote.total_amount_x);
+    quote.dust_y = liquidity_parameter
+        .amount_y
+        .saturating_sub(quote.total_amount_y);
+
+    Ok(quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_provider::fixture_pair;
+    use lb_clmm::instructions::deposit::{StrategyParameters, StrategyType};
+
+    #[tokio::test]
+    async fn test_quote_add_liquidity_by_strategy() {
+        let (_, lb_pair, bin_arrays) = fixture_pair().await;
+
+        let liquidity_parameter = LiquidityParameterByStrategy {
+            amount_x: 1_000_000,
+            amount_y: 1_000_000,
+            active_id: lb_pair.active_id,
+            max_active_bin_slippage: 0,
+            strategy_parameters: StrategyParameters {
+                min_bin_id: lb_pair.active_id - 5,
+                max_bin_id: lb_pair.active_id + 5,
+                strategy_type: StrategyType::SpotImBalanced,
+                parameteres: [0u8; 64],
+            },
+        };
+
+        let quote = quote_add_liquidity_by_strategy(
+            &lb_pair,
+            &bin_arrays,
+            &liquidity_parameter,
+            lb_pair.v_parameters.last_update_timestamp as u64,
+        )
+        .unwrap();
+
+        assert!(!quote.bins.is_empty());
+        assert!(quote.bins.iter().all(|bin| bin.liquidity_share > 0));
+        assert_eq!(
+            quote.total_amount_x + quote.dust_x,
+            liquidity_parameter.amount_x
+        );
+        assert_eq!(
+            quote.total_amount_y + quote.dust_y,
+            liquidity_parameter.amount_y
+        );
+        assert!(quote
+            .bins
+            .iter()
+            .filter(|bin| bin.bin_id != lb_pair.active_id)
+            .all(|bin| bin.composition_fee_x == 0 && bin.composition_fee_y == 0));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::claimable::get_claimable_fee_and_reward;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use lb_clmm::{
+    constants::{BASIS_POINT_MAX, NUM_REWARDS},
+    instructions::withdraw::remove_liquidity::calculate_shares_to_remove,
+    math::{u128x128_math::Rounding, u64x64_math::SCALE_OFFSET, utils_math::safe_mul_shr_cast},
+    state::{
+        bin::{Bin, BinArray},
+        lb_pair::LbPair,
+        position::PositionV2,
+    },
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct BinWithdrawQuote {
+    pub bin_id: i32,
+    /// Liquidity share burned from the position
+    pub liquidity_share: u128,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawQuote {
+    pub bins: Vec<BinWithdrawQuote>,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PositionValue {
+    /// Token X of the liquidity
+    pub amount_x: u64,
+    /// Token Y of the liquidity
+    pub amount_y: u64,
+    /// Pending swap fee in token X
+    pub fee_x: u64,
+    /// Pending swap fee in token Y
+    pub fee_y: u64,
+    /// Pending farming rewards, in the reward token
+    pub rewards: [u64; NUM_REWARDS],
+    /// Liquidity and pending swap fee valued in token Y. Rewards are excluded as they are priced in their own token.
+    pub value_in_y: u64,
+}
+
+pub(crate) fn get_bin(bin_arrays: &HashMap<Pubkey, BinArray>, bin_id: i32) -> Result<Bin> {
+    let bin_array = bin_arrays
+        .values()
+        .find(|bin_array| bin_array.is_bin_id_within_range(bin_id).is_ok())
+        .with_context(|| format!("Bin array of bin {} not found", bin_id))?;
+
+    Ok(*bin_array.get_bin(bin_id)?)
+}
+
+/// Same as get_bin, but bin of a bin array not initialized yet is empty
+pub(crate) fn get_bin_or_empty(bin_arrays: &HashMap<Pubkey, BinArray>, bin_id: i32) -> Result<Bin> {
+    match bin_arrays
+        .values()
+        .find(|bin_array| bin_array.is_bin_id_within_range(bin_id).is_ok())
+    {
+        Some(bin_array) => Ok(*bin_array.get_bin(bin_id)?),
+        None => Ok(Bin::default()),
+    }
+}
+
+/// Quote remove liquidity of bps_per_bin from every bin of the position. Same as the on-chain remove_liquidity_by_range over the whole position.
+pub fn quote_withdraw(
+    position: &PositionV2,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    bps_per_bin: u16,
+) -> Result<WithdrawQuote> {
+    anyhow::ensure!(
+        bps_per_bin > 0 && bps_per_bin <= BASIS_POINT_MAX as u16,
+        "Invalid bps"
+    );
+
+    let mut quote = WithdrawQuote::default();
+
+    for bin_id in position.lower_bin_id..=position.upper_bin_id {
+        let liquidity_share = calculate_shares_to_remove(bps_per_bin, bin_id, position)?;
+        if liquidity_share == 0 {
+            continue;
+        }
+
+        let bin = get_bin(bin_arrays, bin_id)?;
+        let (amount_x, amount_y) = bin.calculate_out_amount(liquidity_share)?;
+
+        quote.amount_x = quote
+            .amount_x
+            .checked_add(amount_x)
+            .context("MathOverflow")?;
+        quote.amount_y = quote
+            .amount_y
+            .checked_add(amount_y)
+            .context("MathOverflow")?;
+
+        quote.bins.push(BinWithdrawQuote {
+            bin_id,
+            liquidity_share,
+            amount_x,
+            amount_y,
+        });
+    }
+
+    Ok(quote)
+}
+
+/// Value of the position at current_timestamp, including claimable swap fees, and rewards. Price is the price of token X in token Y, in Q64.64 lamport unit.
+pub fn position_value(
+    lb_pair: &LbPair,
+    position: &PositionV2,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    price: u128,
+    current_timestamp: u64,
+) -> Result<PositionValue> {
+    let WithdrawQuote {
+        amount_x, amount_y, ..
+    } = quote_withdraw(position, bin_arrays, BASIS_POINT_MAX as u16)?;
+
+    let claimable = get_claimable_fee_and_reward(lb_pair, position, bin_arrays, current_timestamp)?;
+
+    let mut value = PositionValue {
+        amount_x,
+        amount_y,
+        fee_x: claimable.fee_x,
+        fee_y: claimable.fee_y,
+        rewards: claimable.rewards,
+        ..Default::default()
+    };
+
+    let total_x = value
+        .amount_x
+        .checked_add(value.fee_x)
+        .context("MathOverflow")?;
+    let total_y = value
+        .amount_y
+        .checked_add(value.fee_y)
+        .context("MathOverflow")?;
+    let total_x_in_y: u64 = safe_mul_shr_cast(total_x.into(), price, SCALE_OFFSET, Rounding::Down)?;
+
+    value.value_in_y = total_x_in_y.checked_add(total_y).context("MathOverflow")?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_provider::fixture_pair;
+    use lb_clmm::math::price_math::get_price_from_id;
+
+    #[tokio::test]
+    async fn test_quote_withdraw_and_position_value() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays) = fixture_pair().await;
+
+        // Position owning half of the active bin
+        let active_bin = get_bin(&bin_arrays, lb_pair.active_id).unwrap();
+        let mut position: PositionV2 = bytemuck::Zeroable::zeroed();
+        position.lb_pair = lb_pair_pubkey;
+        position.lower_bin_id = lb_pair.active_id;
+        position.upper_bin_id = lb_pair.active_id;
+        position.liquidity_shares[0] = active_bin.liquidity_supply / 2;
+        position.fee_infos[0].fee_x_per_token_complete = active_bin.fee_amount_x_per_token_stored;
+        position.fee_infos[0].fee_y_per_token_complete = active_bin.fee_amount_y_per_token_stored;
+        position.fee_infos[0].fee_x_pending = 100;
+
+        let quote = quote_withdraw(&position, &bin_arrays, 5000).unwrap();
+        let (amount_x, amount_y) = active_bin
+            .calculate_out_amount(active_bin.liquidity_supply / 4)
+            .unwrap();
+        assert_eq!(quote.bins.len(), 1);
+        assert_eq!(quote.amount_x, amount_x);
+        assert_eq!(quote.amount_y, amount_y);
+
+        let price = get_price_from_id(lb_pair.active_id, lb_pair.bin_step).unwrap();
+        let value = position_value(
+            &lb_pair,
+            &position,
+            &bin_arrays,
+            price,
+            lb_pair.v_parameters.last_update_timestamp as u64,
+        )
+        .unwrap();
+        let (amount_x, amount_y) = active_bin
+            .calculate_out_amount(active_bin.liquidity_supply / 2)
+            .unwrap();
+        assert_eq!(value.amount_x, amount_x);
+        assert_eq!(value.amount_y, amount_y);
+        assert_eq!(value.fee_x, 100);
+        assert_eq!(value.fee_y, 0);
+        assert!(value.value_in_y >= value.amount_y);
+    }
+}
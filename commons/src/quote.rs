@@ -1,6 +1,12 @@
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anyhow::{ensure, Context, Result};
 use lb_clmm::{
+    constants::{BASIS_POINT_MAX, NO_FEE_DISCOUNT_MULTIPLIER_BPS},
+    math::{
+        price_math::{get_price_from_id, get_price_impact_limit_bin_id},
+        u128x128_math::Rounding,
+        utils_math::safe_mul_div_cast,
+    },
     pair_action_access::ActivationType,
     state::{
         bin::{Bin, BinArray, SwapResult},
@@ -11,6 +17,37 @@ use lb_clmm::{
 };
 use std::collections::HashMap;
 
+/// Bin array required by the quote is not in the provided bin arrays
+#[derive(Debug)]
+pub struct MissingBinArray(pub Pubkey);
+
+impl std::fmt::Display for MissingBinArray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bin array {} not found", self.0)
+    }
+}
+
+impl std::error::Error for MissingBinArray {}
+
+/// Pool doesn't have enough liquidity to fill the swap
+#[derive(Debug)]
+pub struct PoolOutOfLiquidity;
+
+impl std::fmt::Display for PoolOutOfLiquidity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pool out of liquidity")
+    }
+}
+
+impl std::error::Error for PoolOutOfLiquidity {}
+
+/// Pair as if the quoted swap was executed. Only the bin arrays touched by the swap are included.
+pub(crate) struct SwapSimulation {
+    pub detail: SwapQuoteDetail,
+    pub lb_pair: LbPair,
+    pub bin_arrays: Vec<(Pubkey, BinArray)>,
+}
+
 #[derive(Debug)]
 pub struct SwapExactInQuote {
     pub amount_out: u64,
@@ -23,6 +60,140 @@ pub struct SwapExactOutQuote {
     pub fee: u64,
 }
 
+#[derive(Debug)]
+pub struct SwapExactInWithPriceImpactQuote {
+    /// Amount in consumed by the swap, including fee
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    /// Amount in left once the price bound is hit. Refunded to the user.
+    pub amount_left: u64,
+    pub end_active_id: i32,
+}
+
+/// Swap result of a single bin crossed by the swap
+#[derive(Debug, Clone)]
+pub struct BinSwapDetail {
+    pub bin_id: i32,
+    /// Price of the bin in Q64.64
+    pub price: u128,
+    /// Amount of token swapped into the bin, excluding fee
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Swap fee, includes protocol fee
+    pub fee: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SwapQuoteDetail {
+    /// Total amount in, excluding fee
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Total swap fee, includes protocol fee and host fee
+    pub fee: u64,
+    /// Part of fee
+    pub protocol_fee_after_host_fee: u64,
+    /// Part of protocol fee
+    pub host_fee: u64,
+    pub start_active_id: i32,
+    pub end_active_id: i32,
+    /// Price change between the start and end active bin in BPS
+    pub price_impact_bps: u64,
+    /// Bins crossed, in the order of the swap
+    pub bins: Vec<BinSwapDetail>,
+    /// Bin arrays touched, in the order of the swap
+    pub bin_array_pubkeys: Vec<Pubkey>,
+}
+
+impl SwapQuoteDetail {
+    fn new(start_active_id: i32) -> Self {
+        Self {
+            amount_in: 0,
+            amount_out: 0,
+            fee: 0,
+            protocol_fee_after_host_fee: 0,
+            host_fee: 0,
+            start_active_id,
+            end_active_id: start_active_id,
+            price_impact_bps: 0,
+            bins: vec![],
+            bin_array_pubkeys: vec![],
+        }
+    }
+
+    fn accumulate_bin(
+        &mut self,
+        lb_pair: &LbPair,
+        bin_detail: BinSwapDetail,
+        host_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        let protocol_fee = lb_pair.compute_protocol_fee(bin_detail.fee)?;
+        let host_fee = match host_fee_bps {
+            Some(bps) => {
+                protocol_fee
+                    .checked_mul(bps.into())
+                    .context("MathOverflow")?
+                    / BASIS_POINT_MAX as u64
+            }
+            None => 0,
+        };
+
+        self.amount_in = self
+            .amount_in
+            .checked_add(bin_detail.amount_in)
+            .context("MathOverflow")?;
+        self.amount_out = self
+            .amount_out
+            .checked_add(bin_detail.amount_out)
+            .context("MathOverflow")?;
+        self.fee = self
+            .fee
+            .checked_add(bin_detail.fee)
+            .context("MathOverflow")?;
+        self.protocol_fee_after_host_fee = self
+            .protocol_fee_after_host_fee
+            .checked_add(protocol_fee - host_fee)
+            .context("MathOverflow")?;
+        self.host_fee = self
+            .host_fee
+            .checked_add(host_fee)
+            .context("MathOverflow")?;
+        self.bins.push(bin_detail);
+
+        Ok(())
+    }
+
+    fn add_bin_array(&mut self, bin_array_pubkey: Pubkey) {
+        if !self.bin_array_pubkeys.contains(&bin_array_pubkey) {
+            self.bin_array_pubkeys.push(bin_array_pubkey);
+        }
+    }
+
+    fn finalize(&mut self, end_active_id: i32, bin_step: u16) -> Result<()> {
+        self.end_active_id = end_active_id;
+
+        let start_price = get_price_from_id(self.start_active_id, bin_step)?;
+        let end_price = get_price_from_id(end_active_id, bin_step)?;
+
+        self.price_impact_bps = safe_mul_div_cast(
+            start_price.abs_diff(end_price),
+            BASIS_POINT_MAX as u128,
+            start_price,
+            Rounding::Up,
+        )?;
+
+        Ok(())
+    }
+}
+
+fn get_current_point(lb_pair: &LbPair, current_timestamp: u64, current_slot: u64) -> Result<u64> {
+    let activation_type = ActivationType::try_from(lb_pair.activation_type)?;
+    Ok(match activation_type {
+        ActivationType::Slot => current_slot,
+        ActivationType::Timestamp => current_timestamp,
+    })
+}
+
 fn validate_swap_activation(
     lb_pair: &LbPair,
     current_timestamp: u64,
@@ -35,11 +206,7 @@ fn validate_swap_activation(
 
     let pair_type = lb_pair.pair_type()?;
     if pair_type.eq(&PairType::Permission) {
-        let activation_type = ActivationType::try_from(lb_pair.activation_type)?;
-        let current_point = match activation_type {
-            ActivationType::Slot => current_slot,
-            ActivationType::Timestamp => current_timestamp,
-        };
+        let current_point = get_current_point(lb_pair, current_timestamp, current_slot)?;
 
         ensure!(
             current_point >= lb_pair.activation_point,
@@ -50,23 +217,100 @@ fn validate_swap_activation(
     Ok(())
 }
 
+/// Active bin once the swap enter the bin array. Bin arrays without liquidity in between are skipped by the swap, so it continue from the edge of the bin array.
+fn get_swap_start_bin_id(active_id: i32, bin_array: &BinArray, swap_for_y: bool) -> Result<i32> {
+    if bin_array.is_bin_id_within_range(active_id).is_ok() {
+        return Ok(active_id);
+    }
+
+    let (lower_bin_id, upper_bin_id) =
+        BinArray::get_bin_array_lower_upper_bin_id(bin_array.index as i32)?;
+
+    Ok(if swap_for_y {
+        upper_bin_id
+    } else {
+        lower_bin_id
+    })
+}
+
 pub fn quote_exact_out(
     lb_pair_pubkey: Pubkey,
     lb_pair: &LbPair,
-    mut amount_out: u64,
+    amount_out: u64,
+    swap_for_y: bool,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+) -> Result<SwapExactOutQuote> {
+    quote_exact_out_discounted(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_out,
+        swap_for_y,
+        bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+    )
+}
+
+/// Quote for wallet with fee tier. fee_multiplier_bps can be obtained from FeeTier::get_fee_multiplier_bps.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_out_discounted(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_out: u64,
     swap_for_y: bool,
     bin_arrays: HashMap<Pubkey, BinArray>,
     bitmap_extension: Option<&BinArrayBitmapExtension>,
     current_timestamp: u64,
     current_slot: u64,
+    fee_multiplier_bps: u16,
 ) -> Result<SwapExactOutQuote> {
+    let SwapQuoteDetail { amount_in, fee, .. } = quote_exact_out_detail(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_out,
+        swap_for_y,
+        bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        fee_multiplier_bps,
+        None,
+    )?;
+
+    Ok(SwapExactOutQuote { amount_in, fee })
+}
+
+/// Quote with per bin breakdown. Host fee is split from the protocol fee when host_fee_bps is provided.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_out_detail(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    mut amount_out: u64,
+    swap_for_y: bool,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+    fee_multiplier_bps: u16,
+    host_fee_bps: Option<u16>,
+) -> Result<SwapQuoteDetail> {
     validate_swap_activation(lb_pair, current_timestamp, current_slot)?;
 
     let mut lb_pair = *lb_pair;
     lb_pair.update_references(current_timestamp as i64)?;
+    let fee_modifier = lb_pair.get_swap_fee_modifier(current_slot, swap_for_y, fee_multiplier_bps);
+    lb_pair.update_protocol_share(get_current_point(
+        &lb_pair,
+        current_timestamp,
+        current_slot,
+    )?);
 
-    let mut total_amount_in: u64 = 0;
-    let mut total_fee: u64 = 0;
+    let mut detail = SwapQuoteDetail::new(lb_pair.active_id);
 
     while amount_out > 0 {
         let active_bin_array_pubkey = get_bin_array_pubkeys_for_swap(
@@ -77,12 +321,17 @@ pub fn quote_exact_out(
             1,
         )?
         .pop()
-        .context("Pool out of liquidity")?;
+        .ok_or(PoolOutOfLiquidity)?;
 
         let mut active_bin_array = bin_arrays
             .get(&active_bin_array_pubkey)
             .cloned()
-            .context("Active bin array not found")?;
+            .ok_or(MissingBinArray(active_bin_array_pubkey))?;
+
+        lb_pair.active_id =
+            get_swap_start_bin_id(lb_pair.active_id, &active_bin_array, swap_for_y)?;
+
+        detail.add_bin_array(active_bin_array_pubkey);
 
         loop {
             if active_bin_array
@@ -100,84 +349,231 @@ pub fn quote_exact_out(
 
             if !active_bin.is_empty(!swap_for_y) {
                 let bin_max_amount_out = active_bin.get_max_amount_out(swap_for_y);
-                if amount_out >= bin_max_amount_out {
-                    let max_amount_in = active_bin.get_max_amount_in(price, swap_for_y)?;
-                    let max_fee = lb_pair.compute_fee(max_amount_in)?;
-
-                    total_amount_in = total_amount_in
-                        .checked_add(max_amount_in)
-                        .context("MathOverflow")?;
-
-                    total_fee = total_fee.checked_add(max_fee).context("MathOverflow")?;
-
-                    amount_out = amount_out
-                        .checked_sub(bin_max_amount_out)
-                        .context("MathOverflow")?;
+                let (bin_amount_in, bin_amount_out) = if amount_out >= bin_max_amount_out {
+                    (
+                        active_bin.get_max_amount_in(price, swap_for_y)?,
+                        bin_max_amount_out,
+                    )
                 } else {
-                    let amount_in = Bin::get_amount_in(amount_out, price, swap_for_y)?;
-                    let fee = lb_pair.compute_fee(amount_in)?;
-
-                    total_amount_in = total_amount_in
-                        .checked_add(amount_in)
-                        .context("MathOverflow")?;
-
-                    total_fee = total_fee.checked_add(fee).context("MathOverflow")?;
-
-                    amount_out = 0;
-                }
+                    (
+                        Bin::get_amount_in(amount_out, price, swap_for_y)?,
+                        amount_out,
+                    )
+                };
+                let fee = lb_pair.compute_fee(bin_amount_in, &fee_modifier)?;
+
+                detail.accumulate_bin(
+                    &lb_pair,
+                    BinSwapDetail {
+                        bin_id: lb_pair.active_id,
+                        price,
+                        amount_in: bin_amount_in,
+                        amount_out: bin_amount_out,
+                        fee,
+                    },
+                    host_fee_bps,
+                )?;
+
+                amount_out = amount_out
+                    .checked_sub(bin_amount_out)
+                    .context("MathOverflow")?;
             }
 
             if amount_out > 0 {
-                lb_pair.advance_active_bin(swap_for_y)?;
+                ensure!(
+                    lb_pair.advance_active_bin(swap_for_y).is_ok(),
+                    PoolOutOfLiquidity
+                );
             }
         }
     }
 
-    Ok(SwapExactOutQuote {
-        amount_in: total_amount_in,
-        fee: total_fee,
-    })
+    detail.finalize(lb_pair.active_id, lb_pair.bin_step)?;
+
+    Ok(detail)
 }
 
 pub fn quote_exact_in(
     lb_pair_pubkey: Pubkey,
     lb_pair: &LbPair,
-    mut amount_in: u64,
+    amount_in: u64,
     swap_for_y: bool,
     bin_arrays: HashMap<Pubkey, BinArray>,
     bitmap_extension: Option<&BinArrayBitmapExtension>,
     current_timestamp: u64,
     current_slot: u64,
 ) -> Result<SwapExactInQuote> {
+    quote_exact_in_discounted(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_in,
+        swap_for_y,
+        bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+    )
+}
+
+/// Quote for wallet with fee tier. fee_multiplier_bps can be obtained from FeeTier::get_fee_multiplier_bps.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_in_discounted(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+    fee_multiplier_bps: u16,
+) -> Result<SwapExactInQuote> {
+    let SwapQuoteDetail {
+        amount_out, fee, ..
+    } = quote_exact_in_detail(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_in,
+        swap_for_y,
+        bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        fee_multiplier_bps,
+        None,
+    )?;
+
+    Ok(SwapExactInQuote { amount_out, fee })
+}
+
+/// Quote with per bin breakdown. Host fee is split from the protocol fee when host_fee_bps is provided.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_in_detail(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+    fee_multiplier_bps: u16,
+    host_fee_bps: Option<u16>,
+) -> Result<SwapQuoteDetail> {
+    swap_exact_in_until(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_in,
+        swap_for_y,
+        &bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        fee_multiplier_bps,
+        host_fee_bps,
+        false,
+        |_| false,
+    )
+    .map(|simulation| simulation.detail)
+}
+
+/// Swap exact in on the pair without fee discount, and return the pair after the swap. Swaps can be chained on the result as the on-chain swaps within the same slot.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn simulate_swap_exact_in(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+) -> Result<SwapSimulation> {
+    swap_exact_in_until(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_in,
+        swap_for_y,
+        bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        None,
+        false,
+        |_| false,
+    )
+}
+
+/// Swap amount_in through the bins in the swap direction, with the same fee, and protocol share as the on-chain swap.
+/// Stop once amount_in is consumed, or should_stop return true for the active bin. The bin which stop the swap is not swapped.
+/// When the pool run out of liquidity, the swap so far is returned if partial_fill, else fail as the on-chain swap does.
+#[allow(clippy::too_many_arguments)]
+fn swap_exact_in_until(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+    fee_multiplier_bps: u16,
+    host_fee_bps: Option<u16>,
+    partial_fill: bool,
+    should_stop: impl Fn(i32) -> bool,
+) -> Result<SwapSimulation> {
     validate_swap_activation(lb_pair, current_timestamp, current_slot)?;
 
     let mut lb_pair = *lb_pair;
     lb_pair.update_references(current_timestamp as i64)?;
-
-    let mut total_amount_out: u64 = 0;
-    let mut total_fee: u64 = 0;
-
-    while amount_in > 0 {
-        let active_bin_array_pubkey = get_bin_array_pubkeys_for_swap(
+    let fee_modifier = lb_pair.get_swap_fee_modifier(current_slot, swap_for_y, fee_multiplier_bps);
+    lb_pair.update_protocol_share(get_current_point(
+        &lb_pair,
+        current_timestamp,
+        current_slot,
+    )?);
+
+    let mut detail = SwapQuoteDetail::new(lb_pair.active_id);
+    let mut swapped_bin_arrays = vec![];
+    let mut amount_left = amount_in;
+    let mut is_out_of_liquidity = false;
+
+    while amount_left > 0 && !should_stop(lb_pair.active_id) && !is_out_of_liquidity {
+        let Some(active_bin_array_pubkey) = get_bin_array_pubkeys_for_swap(
             lb_pair_pubkey,
             &lb_pair,
             bitmap_extension,
             swap_for_y,
             1,
         )?
-        .pop()
-        .context("Pool out of liquidity")?;
+        .pop() else {
+            ensure!(partial_fill, PoolOutOfLiquidity);
+            break;
+        };
 
         let mut active_bin_array = bin_arrays
             .get(&active_bin_array_pubkey)
             .cloned()
-            .context("Active bin array not found")?;
+            .ok_or(MissingBinArray(active_bin_array_pubkey))?;
+
+        // Stay if the next bin with liquidity is beyond the stop bin
+        let next_active_id =
+            get_swap_start_bin_id(lb_pair.active_id, &active_bin_array, swap_for_y)?;
+        if should_stop(next_active_id) {
+            break;
+        }
+        lb_pair.active_id = next_active_id;
+
+        detail.add_bin_array(active_bin_array_pubkey);
 
         loop {
             if active_bin_array
                 .is_bin_id_within_range(lb_pair.active_id)
                 .is_err()
-                || amount_in == 0
+                || amount_left == 0
+                || should_stop(lb_pair.active_id)
             {
                 break;
             }
@@ -193,30 +589,195 @@ pub fn quote_exact_in(
                     amount_out,
                     fee,
                     ..
-                } = active_bin.swap(amount_in, price, swap_for_y, &lb_pair, None)?;
-
-                amount_in = amount_in
+                } = active_bin.swap(
+                    amount_left,
+                    price,
+                    swap_for_y,
+                    &lb_pair,
+                    None,
+                    &fee_modifier,
+                )?;
+
+                amount_left = amount_left
                     .checked_sub(amount_in_with_fees)
                     .context("MathOverflow")?;
 
-                total_amount_out = total_amount_out
-                    .checked_add(amount_out)
-                    .context("MathOverflow")?;
-                total_fee = total_fee.checked_add(fee).context("MathOverflow")?;
+                detail.accumulate_bin(
+                    &lb_pair,
+                    BinSwapDetail {
+                        bin_id: lb_pair.active_id,
+                        price,
+                        amount_in: amount_in_with_fees
+                            .checked_sub(fee)
+                            .context("MathOverflow")?,
+                        amount_out,
+                        fee,
+                    },
+                    host_fee_bps,
+                )?;
             }
 
-            if amount_in > 0 {
-                lb_pair.advance_active_bin(swap_for_y)?;
+            if amount_left > 0 && lb_pair.advance_active_bin(swap_for_y).is_err() {
+                ensure!(partial_fill, PoolOutOfLiquidity);
+                is_out_of_liquidity = true;
+                break;
             }
         }
+
+        swapped_bin_arrays.push((active_bin_array_pubkey, active_bin_array));
     }
 
-    Ok(SwapExactInQuote {
-        amount_out: total_amount_out,
-        fee: total_fee,
+    detail.finalize(lb_pair.active_id, lb_pair.bin_step)?;
+
+    // Same as the on-chain swap
+    lb_pair.v_parameters.last_update_timestamp = current_timestamp as i64;
+    lb_pair.record_slot_swap(
+        current_slot,
+        swap_for_y,
+        detail
+            .amount_in
+            .checked_add(detail.fee)
+            .context("MathOverflow")?,
+        detail.amount_out,
+    )?;
+
+    Ok(SwapSimulation {
+        detail,
+        lb_pair,
+        bin_arrays: swapped_bin_arrays,
     })
 }
 
+/// Quote of swap_with_price_impact. Same as the on-chain handler, the price bound is computed from active_id, or the pair active bin when None.
+/// The swap stop once the bound is hit, and the amount left is not transferred from the user. Fail when the pool run out of liquidity before the bound.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_in_with_price_impact(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+) -> Result<SwapExactInWithPriceImpactQuote> {
+    let limit_bin_id = get_price_impact_limit_bin_id(
+        active_id.unwrap_or(lb_pair.active_id),
+        lb_pair.bin_step,
+        swap_for_y,
+        max_price_impact_bps,
+    )?;
+
+    let detail = swap_exact_in_until(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_in,
+        swap_for_y,
+        &bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        None,
+        false,
+        |bin_id| {
+            if swap_for_y {
+                bin_id <= limit_bin_id
+            } else {
+                bin_id >= limit_bin_id
+            }
+        },
+    )?
+    .detail;
+
+    let amount_in_with_fees = detail
+        .amount_in
+        .checked_add(detail.fee)
+        .context("MathOverflow")?;
+
+    Ok(SwapExactInWithPriceImpactQuote {
+        amount_in: amount_in_with_fees,
+        amount_out: detail.amount_out,
+        fee: detail.fee,
+        amount_left: amount_in - amount_in_with_fees,
+        end_active_id: detail.end_active_id,
+    })
+}
+
+/// Largest swap which keep the price within max_price_impact_bps from the active bin price. Same semantic as quote_exact_in_with_price_impact.
+/// The total amount in required is amount_in + fee of the result.
+#[allow(clippy::too_many_arguments)]
+pub fn max_amount_in_for_price_impact(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    swap_for_y: bool,
+    max_price_impact_bps: u16,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+    fee_multiplier_bps: u16,
+) -> Result<SwapQuoteDetail> {
+    let target_bin_id = get_price_impact_limit_bin_id(
+        lb_pair.active_id,
+        lb_pair.bin_step,
+        swap_for_y,
+        max_price_impact_bps,
+    )?;
+
+    amount_in_to_reach_bin(
+        lb_pair_pubkey,
+        lb_pair,
+        swap_for_y,
+        target_bin_id,
+        bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        fee_multiplier_bps,
+    )
+}
+
+/// Swap which consume all liquidity between the active bin and target_bin_id (exclusive). Any further swap will be filled from target_bin_id onward.
+/// Stop early when the pool run out of liquidity. The total amount in required is amount_in + fee of the result.
+#[allow(clippy::too_many_arguments)]
+pub fn amount_in_to_reach_bin(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    swap_for_y: bool,
+    target_bin_id: i32,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    current_timestamp: u64,
+    current_slot: u64,
+    fee_multiplier_bps: u16,
+) -> Result<SwapQuoteDetail> {
+    // Unbounded amount in fill every bin until the target bin
+    swap_exact_in_until(
+        lb_pair_pubkey,
+        lb_pair,
+        u64::MAX,
+        swap_for_y,
+        &bin_arrays,
+        bitmap_extension,
+        current_timestamp,
+        current_slot,
+        fee_multiplier_bps,
+        None,
+        true,
+        |bin_id| {
+            if swap_for_y {
+                bin_id <= target_bin_id
+            } else {
+                bin_id >= target_bin_id
+            }
+        },
+    )
+    .map(|simulation| simulation.detail)
+}
+
 pub fn get_bin_array_pubkeys_for_swap(
     lb_pair_pubkey: Pubkey,
     lb_pair: &LbPair,
@@ -277,243 +838,398 @@ pub fn get_bin_array_pubkeys_for_swap(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use anchor_client::anchor_lang::AccountDeserialize;
-    use anchor_client::solana_sdk::clock::Clock;
-    use anchor_client::{
-        solana_client::nonblocking::rpc_client::RpcClient,
-        solana_sdk::{pubkey::Pubkey, signature::Keypair},
-        Client, Cluster,
-    };
-    use std::{rc::Rc, str::FromStr};
-
-    /// Get on chain clock
-    async fn get_clock(rpc_client: RpcClient) -> Result<Clock> {
-        let clock_account = rpc_client
-            .get_account(&anchor_client::solana_sdk::sysvar::clock::ID)
-            .await?;
-
-        let clock_state: Clock = bincode::deserialize(clock_account.data.as_ref())?;
-
-        Ok(clock_state)
-    }
-
-    #[tokio::test]
-    async fn test_swap_quote_exact_out() {
-        // RPC client. No gPA is required.
-        let rpc_client = RpcClient::new(Cluster::Mainnet.url().to_string());
+    use lb_clmm::constants::MAX_BIN_PER_ARRAY;
 
-        let client = Client::new(
-            Cluster::Custom(rpc_client.url(), rpc_client.url()),
-            Rc::new(Keypair::new()),
-        );
-
-        let program = client.program(lb_clmm::ID).unwrap();
-
-        let SOL_USDC = Pubkey::from_str("HTvjzsfX3yU6BUodCjZ5vZkUrAxMDTrBs3CJaq43ashR").unwrap();
+    async fn load_fixture() -> (Pubkey, LbPair, HashMap<Pubkey, BinArray>, u64, u64) {
+        let (lb_pair_pubkey, lb_pair, bin_arrays) = crate::account_provider::fixture_pair().await;
 
-        let lb_pair = program.account::<LbPair>(SOL_USDC).await.unwrap();
+        let current_timestamp = lb_pair.v_parameters.last_update_timestamp as u64;
+        let current_slot = lb_pair.activation_point;
 
-        // 3 bin arrays to left, and right is enough to cover most of the swap, and stay under 1.4m CU constraint.
-        // Get 3 bin arrays to the left from the active bin
-        let left_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(SOL_USDC, &lb_pair, None, true, 3).unwrap();
-
-        // Get 3 bin arrays to the right the from active bin
-        let right_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(SOL_USDC, &lb_pair, None, false, 3).unwrap();
+        (
+            lb_pair_pubkey,
+            lb_pair,
+            bin_arrays,
+            current_timestamp,
+            current_slot,
+        )
+    }
 
-        // Fetch bin arrays
-        let bin_array_pubkeys = left_bin_array_pubkeys
-            .into_iter()
-            .chain(right_bin_array_pubkeys.into_iter())
-            .collect::<Vec<Pubkey>>();
+    /// Copy of the pair where only the bin arrays of the fixture have liquidity
+    fn with_fixture_liquidity_only(
+        lb_pair: &LbPair,
+        bin_arrays: &HashMap<Pubkey, BinArray>,
+    ) -> LbPair {
+        let mut lb_pair = *lb_pair;
+        lb_pair.bin_array_bitmap = [0u64; 16];
+        for bin_array in bin_arrays.values() {
+            if !bin_array.is_zero_liquidity() {
+                lb_pair
+                    .flip_bin_array_bit(&None, bin_array.index as i32)
+                    .unwrap();
+            }
+        }
+        lb_pair
+    }
 
-        let accounts = rpc_client
-            .get_multiple_accounts(&bin_array_pubkeys)
-            .await
+    #[tokio::test]
+    async fn test_swap_quote_skip_empty_bin_arrays() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays, current_timestamp, current_slot) =
+            load_fixture().await;
+        let mut lb_pair = with_fixture_liquidity_only(&lb_pair, &bin_arrays);
+
+        // Active bin 2 bin arrays below the lowest bin array with liquidity
+        let lowest_index = bin_arrays
+            .values()
+            .filter(|bin_array| !bin_array.is_zero_liquidity())
+            .map(|bin_array| bin_array.index as i32)
+            .min()
             .unwrap();
+        let (lowest_bin_id, _) = BinArray::get_bin_array_lower_upper_bin_id(lowest_index).unwrap();
+        lb_pair.active_id = lowest_bin_id - 2 * MAX_BIN_PER_ARRAY as i32;
 
-        let bin_arrays = accounts
-            .into_iter()
-            .zip(bin_array_pubkeys.into_iter())
-            .map(|(account, key)| {
-                (
-                    key,
-                    BinArray::try_deserialize(&mut account.unwrap().data.as_ref()).unwrap(),
-                )
-            })
-            .collect::<HashMap<_, _>>();
-
-        let usdc_token_multiplier = 1_000_000.0;
-        let sol_token_multiplier = 1_000_000_000.0;
-
-        let out_sol_amount = 1_000_000_000;
-        let clock = get_clock(rpc_client).await.unwrap();
-
-        let quote_result = quote_exact_out(
-            SOL_USDC,
+        let amount_out = 1_000;
+        let detail = quote_exact_out_detail(
+            lb_pair_pubkey,
             &lb_pair,
-            out_sol_amount,
+            amount_out,
             false,
             bin_arrays.clone(),
             None,
-            clock.unix_timestamp as u64,
-            clock.slot,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+            None,
         )
         .unwrap();
 
-        let in_amount = quote_result.amount_in + quote_result.fee;
+        assert_eq!(detail.amount_out, amount_out);
+        assert!(detail.bins[0].bin_id >= lowest_bin_id);
 
-        println!(
-            "{} USDC -> exact 1 SOL",
-            in_amount as f64 / usdc_token_multiplier
-        );
-
-        let quote_result = quote_exact_in(
-            SOL_USDC,
+        let detail = quote_exact_in_detail(
+            lb_pair_pubkey,
             &lb_pair,
-            in_amount,
+            detail.amount_in + detail.fee,
             false,
-            bin_arrays.clone(),
+            bin_arrays,
+            None,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
             None,
-            clock.unix_timestamp as u64,
-            clock.slot,
         )
         .unwrap();
 
-        println!(
-            "{} USDC -> {} SOL",
-            in_amount as f64 / usdc_token_multiplier,
-            quote_result.amount_out as f64 / sol_token_multiplier
-        );
+        assert!(detail.amount_out >= amount_out);
+        assert!(detail.bins[0].bin_id >= lowest_bin_id);
+    }
 
-        let out_usdc_amount = 200_000_000;
+    #[tokio::test]
+    async fn test_swap_quote_out_of_liquidity() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays, current_timestamp, current_slot) =
+            load_fixture().await;
+        let lb_pair = with_fixture_liquidity_only(&lb_pair, &bin_arrays);
+
+        let total_amount_x: u64 = bin_arrays
+            .values()
+            .flat_map(|bin_array| bin_array.bins.iter())
+            .map(|bin| bin.amount_x)
+            .sum();
+
+        let err = quote_exact_out(
+            lb_pair_pubkey,
+            &lb_pair,
+            total_amount_x + 1,
+            false,
+            bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Pool out of liquidity");
 
-        let quote_result = quote_exact_out(
-            SOL_USDC,
+        // All liquidity is consumed before the price bound is hit
+        let detail = max_amount_in_for_price_impact(
+            lb_pair_pubkey,
             &lb_pair,
-            out_usdc_amount,
-            true,
+            false,
+            u16::MAX,
             bin_arrays.clone(),
             None,
-            clock.unix_timestamp as u64,
-            clock.slot,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
         )
         .unwrap();
+        assert_eq!(detail.amount_out, total_amount_x);
 
-        let in_amount = quote_result.amount_in + quote_result.fee;
-
-        println!(
-            "{} SOL -> exact 200 USDC",
-            in_amount as f64 / sol_token_multiplier
-        );
+        let amount_in = detail.amount_in + detail.fee;
+        let err = quote_exact_in(
+            lb_pair_pubkey,
+            &lb_pair,
+            amount_in + 1,
+            false,
+            bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Pool out of liquidity");
 
-        let quote_result = quote_exact_in(
-            SOL_USDC,
+        // Same as the on-chain swap, the price impact swap fail instead of partially filled
+        let err = quote_exact_in_with_price_impact(
+            lb_pair_pubkey,
             &lb_pair,
-            in_amount,
-            true,
+            amount_in + 1,
+            false,
+            None,
+            u16::MAX,
             bin_arrays,
             None,
-            clock.unix_timestamp as u64,
-            clock.slot,
+            current_timestamp,
+            current_slot,
         )
-        .unwrap();
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Pool out of liquidity");
+    }
 
-        println!(
-            "{} SOL -> {} USDC",
-            in_amount as f64 / sol_token_multiplier,
-            quote_result.amount_out as f64 / usdc_token_multiplier
-        );
+    #[tokio::test]
+    async fn test_swap_quote_exact_out() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays, current_timestamp, current_slot) =
+            load_fixture().await;
+
+        for (amount_out, swap_for_y) in [(1_000_000, false), (1_000, true)] {
+            let quote_result = quote_exact_out(
+                lb_pair_pubkey,
+                &lb_pair,
+                amount_out,
+                swap_for_y,
+                bin_arrays.clone(),
+                None,
+                current_timestamp,
+                current_slot,
+            )
+            .unwrap();
+
+            let in_amount = quote_result.amount_in + quote_result.fee;
+            assert!(in_amount > 0);
+
+            // Swapping the quoted amount in get at least the exact amount out
+            let quote_result = quote_exact_in(
+                lb_pair_pubkey,
+                &lb_pair,
+                in_amount,
+                swap_for_y,
+                bin_arrays.clone(),
+                None,
+                current_timestamp,
+                current_slot,
+            )
+            .unwrap();
+
+            assert!(quote_result.amount_out >= amount_out);
+        }
     }
 
     #[tokio::test]
     async fn test_swap_quote_exact_in() {
-        // RPC client. No gPA is required.
-        let rpc_client = RpcClient::new(Cluster::Mainnet.url().to_string());
+        let (lb_pair_pubkey, lb_pair, bin_arrays, current_timestamp, current_slot) =
+            load_fixture().await;
+
+        for (amount_in, swap_for_y) in [(1_000, true), (1_000_000, false)] {
+            let quote_result = quote_exact_in(
+                lb_pair_pubkey,
+                &lb_pair,
+                amount_in,
+                swap_for_y,
+                bin_arrays.clone(),
+                None,
+                current_timestamp,
+                current_slot,
+            )
+            .unwrap();
 
-        let client = Client::new(
-            Cluster::Custom(rpc_client.url(), rpc_client.url()),
-            Rc::new(Keypair::new()),
-        );
+            assert!(quote_result.amount_out > 0);
+            assert!(quote_result.fee > 0);
+
+            // More amount in never get less amount out
+            let double_quote_result = quote_exact_in(
+                lb_pair_pubkey,
+                &lb_pair,
+                amount_in * 2,
+                swap_for_y,
+                bin_arrays.clone(),
+                None,
+                current_timestamp,
+                current_slot,
+            )
+            .unwrap();
 
-        let program = client.program(lb_clmm::ID).unwrap();
+            assert!(double_quote_result.amount_out >= quote_result.amount_out);
+        }
+    }
 
-        let SOL_USDC = Pubkey::from_str("HTvjzsfX3yU6BUodCjZ5vZkUrAxMDTrBs3CJaq43ashR").unwrap();
+    #[tokio::test]
+    async fn test_amount_in_to_reach_bin() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays, current_timestamp, current_slot) =
+            load_fixture().await;
 
-        let lb_pair = program.account::<LbPair>(SOL_USDC).await.unwrap();
+        let target_bin_id = lb_pair.active_id + 5;
 
-        // 3 bin arrays to left, and right is enough to cover most of the swap, and stay under 1.4m CU constraint.
-        // Get 3 bin arrays to the left from the active bin
-        let left_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(SOL_USDC, &lb_pair, None, true, 3).unwrap();
+        let detail = amount_in_to_reach_bin(
+            lb_pair_pubkey,
+            &lb_pair,
+            false,
+            target_bin_id,
+            bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        )
+        .unwrap();
 
-        // Get 3 bin arrays to the right the from active bin
-        let right_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(SOL_USDC, &lb_pair, None, false, 3).unwrap();
+        assert_eq!(detail.end_active_id, target_bin_id);
+        assert!(detail.bins.iter().all(|bin| bin.bin_id < target_bin_id));
 
-        // Fetch bin arrays
-        let bin_array_pubkeys = left_bin_array_pubkeys
-            .into_iter()
-            .chain(right_bin_array_pubkeys.into_iter())
-            .collect::<Vec<Pubkey>>();
+        let quote = quote_exact_in(
+            lb_pair_pubkey,
+            &lb_pair,
+            detail.amount_in + detail.fee,
+            false,
+            bin_arrays,
+            None,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
 
-        let accounts = rpc_client
-            .get_multiple_accounts(&bin_array_pubkeys)
-            .await
-            .unwrap();
+        assert_eq!(quote.amount_out, detail.amount_out);
+        assert_eq!(quote.fee, detail.fee);
+    }
 
-        let bin_arrays = accounts
-            .into_iter()
-            .zip(bin_array_pubkeys.into_iter())
-            .map(|(account, key)| {
-                (
-                    key,
-                    BinArray::try_deserialize(&mut account.unwrap().data.as_ref()).unwrap(),
-                )
-            })
-            .collect::<HashMap<_, _>>();
+    #[tokio::test]
+    async fn test_max_amount_in_for_price_impact() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays, current_timestamp, current_slot) =
+            load_fixture().await;
 
-        // 1 SOL -> USDC
-        let in_sol_amount = 1_000_000_000;
+        let max_price_impact_bps = 500;
 
-        let clock = get_clock(rpc_client).await.unwrap();
+        let detail = max_amount_in_for_price_impact(
+            lb_pair_pubkey,
+            &lb_pair,
+            false,
+            max_price_impact_bps,
+            bin_arrays,
+            None,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        )
+        .unwrap();
 
-        let quote_result = quote_exact_in(
-            SOL_USDC,
+        let last_bin = detail.bins.last().unwrap();
+        let active_price = get_price_from_id(lb_pair.active_id, lb_pair.bin_step).unwrap();
+        let price_impact_bps =
+            (last_bin.price - active_price) * BASIS_POINT_MAX as u128 / active_price;
+
+        assert!(price_impact_bps <= max_price_impact_bps.into());
+        assert!(detail.amount_in > 0);
+    }
+
+    #[tokio::test]
+    async fn test_quote_exact_in_with_price_impact() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays, current_timestamp, current_slot) =
+            load_fixture().await;
+
+        let max_price_impact_bps = 500;
+
+        let max_detail = max_amount_in_for_price_impact(
+            lb_pair_pubkey,
             &lb_pair,
-            in_sol_amount,
-            true,
+            false,
+            max_price_impact_bps,
             bin_arrays.clone(),
             None,
-            clock.unix_timestamp as u64,
-            clock.slot,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
         )
         .unwrap();
+        let max_amount_in = max_detail.amount_in + max_detail.fee;
 
-        println!(
-            "1 SOL -> {:?} USDC",
-            quote_result.amount_out as f64 / 1_000_000.0
-        );
+        // Partial fill, stop at the price bound
+        let amount_in = max_amount_in * 2;
+        let quote = quote_exact_in_with_price_impact(
+            lb_pair_pubkey,
+            &lb_pair,
+            amount_in,
+            false,
+            None,
+            max_price_impact_bps,
+            bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
 
-        // 100 USDC -> SOL
-        let in_usdc_amount = 100_000_000;
+        assert_eq!(quote.amount_in, max_amount_in);
+        assert_eq!(quote.amount_out, max_detail.amount_out);
+        assert_eq!(quote.fee, max_detail.fee);
+        assert_eq!(quote.amount_in + quote.amount_left, amount_in);
 
-        let quote_result = quote_exact_in(
-            SOL_USDC,
+        // Fully filled within the price bound, same as the exact in quote
+        let amount_in = max_amount_in / 2;
+        let quote = quote_exact_in_with_price_impact(
+            lb_pair_pubkey,
             &lb_pair,
-            in_usdc_amount,
+            amount_in,
             false,
+            None,
+            max_price_impact_bps,
             bin_arrays.clone(),
             None,
-            clock.unix_timestamp as u64,
-            clock.slot,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
+        let exact_in_quote = quote_exact_in(
+            lb_pair_pubkey,
+            &lb_pair,
+            amount_in,
+            false,
+            bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
         )
         .unwrap();
 
-        println!(
-            "100 USDC -> {:?} SOL",
-            quote_result.amount_out as f64 / 1_000_000_000.0
-        );
+        assert_eq!(quote.amount_left, 0);
+        assert_eq!(quote.amount_in, amount_in);
+        assert_eq!(quote.amount_out, exact_in_quote.amount_out);
+        assert_eq!(quote.fee, exact_in_quote.fee);
+
+        // Price bound from an active id observed earlier. The bound has been passed, nothing is swapped.
+        let quote = quote_exact_in_with_price_impact(
+            lb_pair_pubkey,
+            &lb_pair,
+            amount_in,
+            false,
+            Some(lb_pair.active_id - 1000),
+            max_price_impact_bps,
+            bin_arrays,
+            None,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
+
+        assert_eq!(quote.amount_in, 0);
+        assert_eq!(quote.amount_left, amount_in);
+        assert_eq!(quote.end_active_id, lb_pair.active_id);
     }
 }
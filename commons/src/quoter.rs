@@ -0,0 +1,223 @@
+use crate::account_provider::{get_anchor_account, get_anchor_accounts, AccountProvider};
+use crate::quote::{
+    get_bin_array_pubkeys_for_swap, quote_exact_in_detail, quote_exact_out_detail, MissingBinArray,
+    SwapExactInQuote, SwapExactOutQuote, SwapQuoteDetail,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use lb_clmm::{
+    constants::NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+    state::{bin::BinArray, bin_array_bitmap_extension::BinArrayBitmapExtension, lb_pair::LbPair},
+    utils::pda::derive_bin_array_bitmap_extension,
+};
+use std::collections::HashMap;
+
+/// Number of bin arrays loaded for the first quote attempt
+pub const INITIAL_BIN_ARRAY_COUNT: u8 = 3;
+
+/// Quote swap of a pair. Accounts are loaded from the provider on every quote, and bin arrays are loaded on demand until the swap is filled.
+pub struct Quoter<P: AccountProvider> {
+    provider: P,
+    lb_pair_pubkey: Pubkey,
+}
+
+impl<P: AccountProvider> Quoter<P> {
+    pub fn new(provider: P, lb_pair_pubkey: Pubkey) -> Self {
+        Self {
+            provider,
+            lb_pair_pubkey,
+        }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    pub async fn quote_exact_in(
+        &self,
+        amount_in: u64,
+        swap_for_y: bool,
+        current_timestamp: u64,
+        current_slot: u64,
+    ) -> Result<SwapExactInQuote> {
+        let SwapQuoteDetail {
+            amount_out, fee, ..
+        } = self
+            .quote_exact_in_detail(
+                amount_in,
+                swap_for_y,
+                current_timestamp,
+                current_slot,
+                NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+                None,
+            )
+            .await?;
+
+        Ok(SwapExactInQuote { amount_out, fee })
+    }
+
+    pub async fn quote_exact_out(
+        &self,
+        amount_out: u64,
+        swap_for_y: bool,
+        current_timestamp: u64,
+        current_slot: u64,
+    ) -> Result<SwapExactOutQuote> {
+        let SwapQuoteDetail { amount_in, fee, .. } = self
+            .quote_exact_out_detail(
+                amount_out,
+                swap_for_y,
+                current_timestamp,
+                current_slot,
+                NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+                None,
+            )
+            .await?;
+
+        Ok(SwapExactOutQuote { amount_in, fee })
+    }
+
+    pub async fn quote_exact_in_detail(
+        &self,
+        amount_in: u64,
+        swap_for_y: bool,
+        current_timestamp: u64,
+        current_slot: u64,
+        fee_multiplier_bps: u16,
+        host_fee_bps: Option<u16>,
+    ) -> Result<SwapQuoteDetail> {
+        self.quote_with_bin_arrays(swap_for_y, |lb_pair, bin_arrays, bitmap_extension| {
+            quote_exact_in_detail(
+                self.lb_pair_pubkey,
+                lb_pair,
+                amount_in,
+                swap_for_y,
+                bin_arrays,
+                bitmap_extension,
+                current_timestamp,
+                current_slot,
+                fee_multiplier_bps,
+                host_fee_bps,
+            )
+        })
+        .await
+    }
+
+    pub async fn quote_exact_out_detail(
+        &self,
+        amount_out: u64,
+        swap_for_y: bool,
+        current_timestamp: u64,
+        current_slot: u64,
+        fee_multiplier_bps: u16,
+        host_fee_bps: Option<u16>,
+    ) -> Result<SwapQuoteDetail> {
+        self.quote_with_bin_arrays(swap_for_y, |lb_pair, bin_arrays, bitmap_extension| {
+            quote_exact_out_detail(
+                self.lb_pair_pubkey,
+                lb_pair,
+                amount_out,
+                swap_for_y,
+                bin_arrays,
+                bitmap_extension,
+                current_timestamp,
+                current_slot,
+                fee_multiplier_bps,
+                host_fee_bps,
+            )
+        })
+        .await
+    }
+
+    /// Load the pair, and bitmap extension. Then retry the quote with more bin arrays while it is missing a bin array, until there's no more bin arrays with liquidity.
+    async fn quote_with_bin_arrays<T>(
+        &self,
+        swap_for_y: bool,
+        quote_fn: impl Fn(
+            &LbPair,
+            HashMap<Pubkey, BinArray>,
+            Option<&BinArrayBitmapExtension>,
+        ) -> Result<T>,
+    ) -> Result<T> {
+        let lb_pair: LbPair = get_anchor_account(&self.provider, &self.lb_pair_pubkey)
+            .await?
+            .context("Pair not found")?;
+
+        let (bitmap_extension_key, _bump) = derive_bin_array_bitmap_extension(self.lb_pair_pubkey);
+        let bitmap_extension: Option<BinArrayBitmapExtension> =
+            get_anchor_account(&self.provider, &bitmap_extension_key).await?;
+
+        let mut bin_arrays = HashMap::new();
+        let mut take_count = INITIAL_BIN_ARRAY_COUNT;
+
+        loop {
+            let bin_array_pubkeys = get_bin_array_pubkeys_for_swap(
+                self.lb_pair_pubkey,
+                &lb_pair,
+                bitmap_extension.as_ref(),
+                swap_for_y,
+                take_count,
+            )?;
+
+            let missing_bin_array_pubkeys = bin_array_pubkeys
+                .iter()
+                .filter(|pubkey| !bin_arrays.contains_key(*pubkey))
+                .copied()
+                .collect::<Vec<_>>();
+
+            bin_arrays.extend(
+                get_anchor_accounts::<BinArray>(&self.provider, &missing_bin_array_pubkeys).await?,
+            );
+
+            let result = quote_fn(&lb_pair, bin_arrays.clone(), bitmap_extension.as_ref());
+
+            // Less bin arrays than requested means all bin arrays with liquidity were loaded
+            let exhausted = bin_array_pubkeys.len() < take_count as usize || take_count == u8::MAX;
+
+            match result {
+                Err(err) if !exhausted && err.downcast_ref::<MissingBinArray>().is_some() => {
+                    take_count = take_count.saturating_mul(2);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_provider::{fixture_pair, fixture_provider};
+    use crate::quote::quote_exact_in;
+
+    #[tokio::test]
+    async fn test_quoter_exact_in_from_snapshot() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays) = fixture_pair().await;
+        let current_timestamp = lb_pair.v_parameters.last_update_timestamp as u64;
+        let current_slot = lb_pair.activation_point;
+
+        let amount_in = 100_000;
+        let expected = quote_exact_in(
+            lb_pair_pubkey,
+            &lb_pair,
+            amount_in,
+            false,
+            bin_arrays,
+            None,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
+
+        let (_, provider) = fixture_provider();
+        let quoter = Quoter::new(provider, lb_pair_pubkey);
+        let quote = quoter
+            .quote_exact_in(amount_in, false, current_timestamp, current_slot)
+            .await
+            .unwrap();
+
+        assert_eq!(quote.amount_out, expected.amount_out);
+        assert_eq!(quote.fee, expected.fee);
+        assert!(quote.amount_out > 0);
+    }
+}
@@ -0,0 +1,545 @@
+use crate::account_provider::{get_anchor_account, get_anchor_accounts, AccountProvider};
+use crate::quote::{
+    get_bin_array_pubkeys_for_swap, simulate_swap_exact_in, PoolOutOfLiquidity, SwapSimulation,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use lb_clmm::{
+    state::{bin::BinArray, bin_array_bitmap_extension::BinArrayBitmapExtension, lb_pair::LbPair},
+    utils::pda::derive_bin_array_bitmap_extension,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Number of chunks the amount is divided into when splitting a hop across parallel pairs
+pub const SPLIT_STEPS: u64 = 20;
+
+/// Pair state used for routing. Bin arrays from both side of the active bin are required to quote either direction.
+#[derive(Clone)]
+pub struct PairSnapshot {
+    pub lb_pair: LbPair,
+    pub bin_arrays: HashMap<Pubkey, BinArray>,
+    pub bitmap_extension: Option<BinArrayBitmapExtension>,
+}
+
+impl PairSnapshot {
+    /// Load the pair, and bin_array_count bin arrays with liquidity on each side of the active bin
+    pub async fn load(
+        provider: &impl AccountProvider,
+        lb_pair_pubkey: Pubkey,
+        bin_array_count: u8,
+    ) -> Result<Self> {
+        let lb_pair: LbPair = get_anchor_account(provider, &lb_pair_pubkey)
+            .await?
+            .context("Pair not found")?;
+
+        let (bitmap_extension_key, _bump) = derive_bin_array_bitmap_extension(lb_pair_pubkey);
+        let bitmap_extension: Option<BinArrayBitmapExtension> =
+            get_anchor_account(provider, &bitmap_extension_key).await?;
+
+        let mut bin_array_pubkeys = get_bin_array_pubkeys_for_swap(
+            lb_pair_pubkey,
+            &lb_pair,
+            bitmap_extension.as_ref(),
+            true,
+            bin_array_count,
+        )?;
+        bin_array_pubkeys.extend(get_bin_array_pubkeys_for_swap(
+            lb_pair_pubkey,
+            &lb_pair,
+            bitmap_extension.as_ref(),
+            false,
+            bin_array_count,
+        )?);
+
+        let bin_arrays = get_anchor_accounts::<BinArray>(provider, &bin_array_pubkeys).await?;
+
+        Ok(Self {
+            lb_pair,
+            bin_arrays,
+            bitmap_extension,
+        })
+    }
+
+    fn has_mints(&self, token_a: Pubkey, token_b: Pubkey) -> bool {
+        (self.lb_pair.token_x_mint == token_a && self.lb_pair.token_y_mint == token_b)
+            || (self.lb_pair.token_x_mint == token_b && self.lb_pair.token_y_mint == token_a)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteSplit {
+    pub lb_pair: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteHop {
+    pub token_in: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Pairs the hop is routed through. More than one when the hop is split across pairs of different bin step.
+    pub splits: Vec<RouteSplit>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub hops: Vec<RouteHop>,
+}
+
+/// Split amount_in across the pairs. The amount is allocated chunk by chunk to the pair with the best output, as if the previous chunks were swapped. None if the pairs can't fill the amount.
+fn quote_hop(
+    pairs: &[(Pubkey, &PairSnapshot)],
+    token_in: Pubkey,
+    token_out: Pubkey,
+    amount_in: u64,
+    current_timestamp: u64,
+    current_slot: u64,
+) -> Result<Option<RouteHop>> {
+    // Pair state after the chunks allocated so far
+    let mut states = pairs
+        .iter()
+        .map(|(_, snapshot)| (snapshot.lb_pair, snapshot.bin_arrays.clone()))
+        .collect::<Vec<_>>();
+    let mut splits = pairs
+        .iter()
+        .map(|(lb_pair, _)| RouteSplit {
+            lb_pair: *lb_pair,
+            amount_in: 0,
+            amount_out: 0,
+            fee: 0,
+        })
+        .collect::<Vec<_>>();
+
+    let chunk = std::cmp::max(amount_in / SPLIT_STEPS, 1);
+    let mut amount_left = amount_in;
+
+    while amount_left > 0 {
+        // Last chunk take the remainder
+        let chunk = if amount_left < chunk * 2 {
+            amount_left
+        } else {
+            chunk
+        };
+
+        let mut best: Option<(usize, SwapSimulation)> = None;
+        for (idx, ((lb_pair_pubkey, snapshot), (lb_pair, bin_arrays))) in
+            pairs.iter().zip(states.iter()).enumerate()
+        {
+            let simulation = match simulate_swap_exact_in(
+                *lb_pair_pubkey,
+                lb_pair,
+                chunk,
+                lb_pair.token_x_mint == token_in,
+                bin_arrays,
+                snapshot.bitmap_extension.as_ref(),
+                current_timestamp,
+                current_slot,
+            ) {
+                Ok(simulation) => simulation,
+                Err(err) if err.is::<PoolOutOfLiquidity>() => continue,
+                Err(err) => return Err(err),
+            };
+
+            let is_better = match &best {
+                Some((_, best_simulation)) => {
+                    simulation.detail.amount_out > best_simulation.detail.amount_out
+                }
+                None => true,
+            };
+            if is_better {
+                best = Some((idx, simulation));
+            }
+        }
+
+        let Some((idx, simulation)) = best else {
+            return Ok(None);
+        };
+
+        let (lb_pair, bin_arrays) = &mut states[idx];
+        *lb_pair = simulation.lb_pair;
+        bin_arrays.extend(simulation.bin_arrays);
+
+        let split = &mut splits[idx];
+        split.amount_in += chunk;
+        split.amount_out += simulation.detail.amount_out;
+        split.fee += simulation.detail.fee;
+
+        amount_left -= chunk;
+    }
+
+    splits.retain(|split| split.amount_in > 0);
+
+    Ok(Some(RouteHop {
+        token_in,
+        token_out,
+        amount_in,
+        amount_out: splits.iter().map(|split| split.amount_out).sum(),
+        splits,
+    }))
+}
+
+/// Best route for swapping amount_in of token_in to token_out through one, or two hops. Each hop is split across the parallel pairs of the same mints.
+pub fn find_best_route(
+    pairs: &HashMap<Pubkey, PairSnapshot>,
+    token_in: Pubkey,
+    token_out: Pubkey,
+    amount_in: u64,
+    current_timestamp: u64,
+    current_slot: u64,
+) -> Result<Route> {
+    let pairs_of = |token_a: Pubkey, token_b: Pubkey| {
+        pairs
+            .iter()
+            .filter(|(_, snapshot)| snapshot.has_mints(token_a, token_b))
+            .map(|(lb_pair, snapshot)| (*lb_pair, snapshot))
+            .collect::<Vec<_>>()
+    };
+
+    let mut best_route: Option<Route> = None;
+    let mut consider = |hops: Vec<RouteHop>| {
+        let amount_out = hops.last().map(|hop| hop.amount_out).unwrap_or(0);
+        let is_better = match &best_route {
+            Some(route) => amount_out > route.amount_out,
+            None => true,
+        };
+        if is_better {
+            best_route = Some(Route {
+                amount_in,
+                amount_out,
+                hops,
+            });
+        }
+    };
+
+    // Direct
+    let direct_pairs = pairs_of(token_in, token_out);
+    if !direct_pairs.is_empty() {
+        if let Some(hop) = quote_hop(
+            &direct_pairs,
+            token_in,
+            token_out,
+            amount_in,
+            current_timestamp,
+            current_slot,
+        )? {
+            consider(vec![hop]);
+        }
+    }
+
+    // Through an intermediate token
+    let intermediate_tokens = pairs
+        .values()
+        .filter_map(|snapshot| {
+            if snapshot.lb_pair.token_x_mint == token_in {
+                Some(snapshot.lb_pair.token_y_mint)
+            } else if snapshot.lb_pair.token_y_mint == token_in {
+                Some(snapshot.lb_pair.token_x_mint)
+            } else {
+                None
+            }
+        })
+        .filter(|token| *token != token_out)
+        .collect::<HashSet<_>>();
+
+    for intermediate_token in intermediate_tokens {
+        let second_pairs = pairs_of(intermediate_token, token_out);
+        if second_pairs.is_empty() {
+            continue;
+        }
+
+        let Some(first_hop) = quote_hop(
+            &pairs_of(token_in, intermediate_token),
+            token_in,
+            intermediate_token,
+            amount_in,
+            current_timestamp,
+            current_slot,
+        )?
+        else {
+            continue;
+        };
+
+        let Some(second_hop) = quote_hop(
+            &second_pairs,
+            intermediate_token,
+            token_out,
+            first_hop.amount_out,
+            current_timestamp,
+            current_slot,
+        )?
+        else {
+            continue;
+        };
+
+        consider(vec![first_hop, second_hop]);
+    }
+
+    best_route.context("No route found")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_provider::fixture_pair;
+    use crate::quote::{max_amount_in_for_price_impact, quote_exact_in, MissingBinArray};
+    use lb_clmm::{constants::NO_FEE_DISCOUNT_MULTIPLIER_BPS, utils::pda::derive_bin_array_pda};
+
+    /// Copy of the snapshot under another pair address, with the bin arrays re-keyed to the pair
+    fn clone_as(snapshot: &PairSnapshot, lb_pair_pubkey: Pubkey) -> PairSnapshot {
+        let bin_arrays = snapshot
+            .bin_arrays
+            .values()
+            .map(|bin_array| {
+                let (bin_array_pubkey, _bump) =
+                    derive_bin_array_pda(lb_pair_pubkey, bin_array.index);
+                (bin_array_pubkey, *bin_array)
+            })
+            .collect();
+
+        PairSnapshot {
+            lb_pair: snapshot.lb_pair,
+            bin_arrays,
+            bitmap_extension: None,
+        }
+    }
+
+    /// Copy of the snapshot under another pair address with another bin step, over the same bin prices
+    fn clone_with_bin_step(
+        snapshot: &PairSnapshot,
+        lb_pair_pubkey: Pubkey,
+        bin_step: u16,
+    ) -> PairSnapshot {
+        let mut snapshot = clone_as(snapshot, lb_pair_pubkey);
+        let original_bin_step = snapshot.lb_pair.bin_step;
+        for bin_array in snapshot.bin_arrays.values_mut() {
+            let (lower_bin_id, _upper_bin_id) =
+                BinArray::get_bin_array_lower_upper_bin_id(bin_array.index as i32).unwrap();
+            for (bin_id, bin) in (lower_bin_id..).zip(bin_array.bins.iter_mut()) {
+                bin.get_or_store_bin_price(bin_id, original_bin_step)
+                    .unwrap();
+            }
+        }
+        snapshot.lb_pair.bin_step = bin_step;
+        snapshot
+    }
+
+    #[tokio::test]
+    async fn test_find_best_route_different_bin_step() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays) = fixture_pair().await;
+        let snapshot = PairSnapshot {
+            lb_pair,
+            bin_arrays,
+            bitmap_extension: None,
+        };
+        let current_timestamp = snapshot.lb_pair.v_parameters.last_update_timestamp as u64;
+        let current_slot = snapshot.lb_pair.activation_point;
+        let token_x = snapshot.lb_pair.token_x_mint;
+        let token_y = snapshot.lb_pair.token_y_mint;
+
+        // Higher bin step charge a higher base fee
+        let parallel_pair_pubkey = Pubkey::new_unique();
+        let parallel_pair = clone_with_bin_step(
+            &snapshot,
+            parallel_pair_pubkey,
+            snapshot.lb_pair.bin_step * 2,
+        );
+
+        let detail = max_amount_in_for_price_impact(
+            lb_pair_pubkey,
+            &snapshot.lb_pair,
+            false,
+            2000,
+            snapshot.bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        )
+        .unwrap();
+        let amount_in = detail.amount_in + detail.fee;
+
+        let mut single_pair_amount_outs = vec![];
+        for (pubkey, pair) in [
+            (lb_pair_pubkey, &snapshot),
+            (parallel_pair_pubkey, &parallel_pair),
+        ] {
+            let quote = quote_exact_in(
+                pubkey,
+                &pair.lb_pair,
+                amount_in,
+                false,
+                pair.bin_arrays.clone(),
+                None,
+                current_timestamp,
+                current_slot,
+            )
+            .unwrap();
+            single_pair_amount_outs.push(quote.amount_out);
+        }
+        // Same liquidity, but higher fee
+        assert!(single_pair_amount_outs[1] < single_pair_amount_outs[0]);
+
+        let mut pairs = HashMap::new();
+        pairs.insert(lb_pair_pubkey, snapshot.clone());
+        pairs.insert(parallel_pair_pubkey, parallel_pair);
+
+        let route = find_best_route(
+            &pairs,
+            token_y,
+            token_x,
+            amount_in,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
+
+        let hop = &route.hops[0];
+        assert_eq!(
+            hop.splits.iter().map(|split| split.amount_in).sum::<u64>(),
+            amount_in
+        );
+        assert!(route.amount_out > single_pair_amount_outs[0]);
+
+        // Cheaper pair take the larger share
+        let split_amount_in = |lb_pair: Pubkey| {
+            hop.splits
+                .iter()
+                .find(|split| split.lb_pair == lb_pair)
+                .map(|split| split.amount_in)
+                .unwrap_or(0)
+        };
+        assert!(split_amount_in(lb_pair_pubkey) > split_amount_in(parallel_pair_pubkey));
+
+        // Missing bin array isn't skipped as out of liquidity
+        let mut pairs = HashMap::new();
+        pairs.insert(
+            lb_pair_pubkey,
+            PairSnapshot {
+                bin_arrays: HashMap::new(),
+                ..snapshot
+            },
+        );
+        let err = find_best_route(
+            &pairs,
+            token_y,
+            token_x,
+            amount_in,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap_err();
+        assert!(err.is::<MissingBinArray>());
+    }
+
+    #[tokio::test]
+    async fn test_find_best_route() {
+        let (lb_pair_pubkey, lb_pair, bin_arrays) = fixture_pair().await;
+        let snapshot = PairSnapshot {
+            lb_pair,
+            bin_arrays,
+            bitmap_extension: None,
+        };
+        let current_timestamp = snapshot.lb_pair.v_parameters.last_update_timestamp as u64;
+        let current_slot = snapshot.lb_pair.activation_point;
+        let token_x = snapshot.lb_pair.token_x_mint;
+        let token_y = snapshot.lb_pair.token_y_mint;
+
+        // Amount which cross multiple bins
+        let detail = max_amount_in_for_price_impact(
+            lb_pair_pubkey,
+            &snapshot.lb_pair,
+            false,
+            2000,
+            snapshot.bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        )
+        .unwrap();
+        let amount_in = detail.amount_in + detail.fee;
+
+        let single_pair_quote = quote_exact_in(
+            lb_pair_pubkey,
+            &snapshot.lb_pair,
+            amount_in,
+            false,
+            snapshot.bin_arrays.clone(),
+            None,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
+
+        // Parallel pair of the same mints
+        let parallel_pair_pubkey = Pubkey::new_unique();
+        let mut pairs = HashMap::new();
+        pairs.insert(lb_pair_pubkey, snapshot.clone());
+        pairs.insert(
+            parallel_pair_pubkey,
+            clone_as(&snapshot, parallel_pair_pubkey),
+        );
+
+        let route = find_best_route(
+            &pairs,
+            token_y,
+            token_x,
+            amount_in,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
+
+        assert_eq!(route.hops.len(), 1);
+        let hop = &route.hops[0];
+        assert_eq!(hop.splits.len(), 2);
+        assert_eq!(
+            hop.splits.iter().map(|split| split.amount_in).sum::<u64>(),
+            amount_in
+        );
+        assert!(route.amount_out > single_pair_quote.amount_out);
+
+        // Two hops through token Y
+        let token_z = Pubkey::new_unique();
+        let second_pair_pubkey = Pubkey::new_unique();
+        let mut second_pair = clone_as(&snapshot, second_pair_pubkey);
+        second_pair.lb_pair.token_x_mint = token_y;
+        second_pair.lb_pair.token_y_mint = token_z;
+
+        let mut pairs = HashMap::new();
+        pairs.insert(lb_pair_pubkey, snapshot);
+        pairs.insert(second_pair_pubkey, second_pair);
+
+        let amount_in = 1_000_000;
+        let route = find_best_route(
+            &pairs,
+            token_x,
+            token_z,
+            amount_in,
+            current_timestamp,
+            current_slot,
+        )
+        .unwrap();
+
+        assert_eq!(route.hops.len(), 2);
+        assert_eq!(route.hops[0].token_out, token_y);
+        assert_eq!(route.hops[1].amount_in, route.hops[0].amount_out);
+        assert_eq!(route.amount_out, route.hops[1].amount_out);
+
+        // No pair to the token
+        assert!(find_best_route(
+            &pairs,
+            token_x,
+            Pubkey::new_unique(),
+            amount_in,
+            current_timestamp,
+            current_slot,
+        )
+        .is_err());
+    }
+}
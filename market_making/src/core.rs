@@ -25,6 +25,8 @@ use anchor_spl::token::spl_token;
 use anchor_spl::token::Mint;
 use anchor_spl::token::TokenAccount;
 use anyhow::{self, Result, Error, Context};
+use commons::account_provider::deserialize_anchor_account;
+use commons::liquidity::quote_add_liquidity_by_strategy;
 use lb_clmm::accounts;
 use lb_clmm::constants::MAX_BIN_PER_ARRAY;
 use lb_clmm::constants::MAX_BIN_PER_POSITION;
@@ -671,6 +673,47 @@ impl Core {
         let user_token_y =
             get_associated_token_address(&payer.pubkey(), &lb_pair_state.token_y_mint);
 
+        let liquidity_parameter = LiquidityParameterByStrategy {
+            amount_x,
+            amount_y,
+            active_id: lb_pair_state.active_id,
+            max_active_bin_slippage: 3,
+            strategy_parameters: StrategyParameters {
+                min_bin_id: lower_bin_id,
+                max_bin_id: upper_bin_id,
+                strategy_type: StrategyType::SpotBalanced,
+                parameteres: [0u8; 64],
+            },
+        };
+
+        // Preview the deposit. Bin arrays not initialized yet are empty.
+        let bin_array_pubkeys = [bin_array_lower, bin_array_upper];
+        let mut bin_arrays = HashMap::new();
+        for (bin_array_pubkey, account) in bin_array_pubkeys
+            .into_iter()
+            .zip(program.rpc().get_multiple_accounts(&bin_array_pubkeys)?)
+        {
+            if let Some(account) = account {
+                bin_arrays.insert(
+                    bin_array_pubkey,
+                    deserialize_anchor_account::<BinArray>(&account.data)?,
+                );
+            }
+        }
+        let quote = quote_add_liquidity_by_strategy(
+            &lb_pair_state,
+            &bin_arrays,
+            &liquidity_parameter,
+            get_epoch_sec(),
+        )?;
+        info!(
+            "deposit quote {} {} composition fee {} {}",
+            quote.total_amount_x,
+            quote.total_amount_y,
+            quote.composition_fee_x,
+            quote.composition_fee_y
+        );
+
         instructions.push(Instruction {
             program_id: lb_clmm::ID,
             accounts: accounts::ModifyLiquidity {
@@ -693,18 +736,7 @@ impl Core {
             }
             .to_account_metas(None),
             data: instruction::AddLiquidityByStrategy {
-                liquidity_parameter: LiquidityParameterByStrategy {
-                    amount_x,
-                    amount_y,
-                    active_id: lb_pair_state.active_id,
-                    max_active_bin_slippage: 3,
-                    strategy_parameters: StrategyParameters {
-                        min_bin_id: lower_bin_id,
-                        max_bin_id: upper_bin_id,
-                        strategy_type: StrategyType::SpotBalanced,
-                        parameteres: [0u8; 64],
-                    },
-                },
+                liquidity_parameter,
             }
             .data(),
         });
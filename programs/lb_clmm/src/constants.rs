@@ -33,6 +33,22 @@ pub const FEE_PRECISION: u64 = 1_000_000_000;
 #[constant]
 pub const MAX_PROTOCOL_SHARE: u16 = 2_500;
 
+/// Maximum volatility surcharge fee. 10%
+#[constant]
+pub const MAX_VOLATILITY_SURCHARGE_BPS: u16 = 1_000;
+
+/// Maximum keeper bounty of position trigger. 1%
+#[constant]
+pub const MAX_KEEPER_BOUNTY_BPS: u16 = 100;
+
+/// Maximum swap fee discount of a fee tier. 50%
+#[constant]
+pub const MAX_FEE_DISCOUNT_BPS: u16 = 5_000;
+
+/// Swap fee multiplier of wallet without fee tier. 100%
+#[constant]
+pub const NO_FEE_DISCOUNT_MULTIPLIER_BPS: u16 = 10_000;
+
 /// Host fee. 20%
 #[constant]
 pub const HOST_FEE_BPS: u16 = 2_000;
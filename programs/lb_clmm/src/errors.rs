@@ -200,4 +200,49 @@ pub enum LBError {
 
     #[msg("Already pass pre-activation swap point")]
     AlreadyPassPreActivationSwapPoint,
+
+    #[msg("Swap deadline exceeded")]
+    SwapDeadlineExceeded,
+
+    #[msg("Whitelist is not enabled")]
+    WhitelistNotEnabled,
+
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Exceeded whitelisted wallet cap")]
+    ExceededWhitelistCap,
+
+    #[msg("Invalid position trigger")]
+    InvalidPositionTrigger,
+
+    #[msg("Position trigger is not fired")]
+    PositionTriggerNotFired,
+
+    #[msg("Invalid range order")]
+    InvalidRangeOrder,
+
+    #[msg("Range order is not filled")]
+    RangeOrderNotFilled,
+
+    #[msg("Invalid oracle length")]
+    InvalidOracleLength,
+
+    #[msg("Pair is not closed, or disabled")]
+    PairNotClosedOrDisabled,
+
+    #[msg("Pair is not empty")]
+    PairNotEmpty,
+
+    #[msg("Invalid protocol share step down")]
+    InvalidProtocolShareStepDown,
+
+    #[msg("Invalid fee discount")]
+    InvalidFeeDiscount,
+
+    #[msg("Fee parameters of the pair were overridden by the admin")]
+    FeeParametersOverridden,
+
+    #[msg("Bootstrap liquidity range is out of the active bin array")]
+    BootstrapRangeOutOfBinArray,
 }
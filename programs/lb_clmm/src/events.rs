@@ -187,6 +187,8 @@ pub struct FeeParameterUpdate {
     pub protocol_share: u16,
     // Base factor of base fee rate
     pub base_factor: u16,
+    // Volatility surcharge fee in BPS
+    pub volatility_surcharge_bps: u16,
 }
 
 #[event]
@@ -197,6 +199,22 @@ pub struct IncreaseObservation {
     pub new_observation_length: u64,
 }
 
+#[event]
+pub struct DecreaseObservation {
+    // Oracle address
+    pub oracle: Pubkey,
+    // Oracle length
+    pub new_observation_length: u64,
+}
+
+#[event]
+pub struct CloseOracle {
+    // Oracle address
+    pub oracle: Pubkey,
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+}
+
 #[event]
 pub struct WithdrawIneligibleReward {
     // Liquidity pool pair
@@ -230,3 +248,167 @@ pub struct UpdatePositionLockReleasePoint {
     // Sender public key
     pub sender: Pubkey,
 }
+
+#[event]
+pub struct UpdatePositionTrigger {
+    // Position public key
+    pub position: Pubkey,
+    // Trigger when the price bin <= lower_bin_id
+    pub lower_bin_id: i32,
+    // Trigger when the price bin >= upper_bin_id
+    pub upper_bin_id: i32,
+    // TWAP window in seconds. 0 = active bin
+    pub twap_window: u32,
+    // Keeper bounty in BPS
+    pub keeper_bounty_bps: u16,
+    // Whether the trigger is enabled
+    pub enabled: bool,
+}
+
+#[event]
+pub struct ExecutePositionTrigger {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Position public key
+    pub position: Pubkey,
+    // Keeper executed the trigger
+    pub keeper: Pubkey,
+    // Price bin which fired the trigger
+    pub price_bin_id: i32,
+    // Amount of token X, and Y withdrawn to the position owner
+    pub amounts: [u64; 2],
+    // Amount of token X, and Y paid to the keeper
+    pub keeper_bounties: [u64; 2],
+}
+
+#[event]
+pub struct UpdateRangeOrder {
+    // Position public key
+    pub position: Pubkey,
+    // Range order side. 0 = None, 1 = Ask, 2 = Bid
+    pub range_order_side: u8,
+}
+
+#[event]
+pub struct CloseRangeOrder {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Position public key
+    pub position: Pubkey,
+    // Position owner
+    pub owner: Pubkey,
+    // Amount of token X, and Y withdrawn to the position owner
+    pub amounts: [u64; 2],
+    // Amount of fee X, and Y claimed to the position owner
+    pub fees: [u64; 2],
+    // Whether the position is closed. Position with pending rewards is kept open for the owner to claim.
+    pub is_closed: bool,
+}
+
+#[event]
+pub struct PresetParameterUpdate {
+    // Preset parameter public key
+    pub preset_parameter: Pubkey,
+    // Version after the update
+    pub version: u64,
+    // Filter period
+    pub filter_period: u16,
+    // Decay period
+    pub decay_period: u16,
+    // Reduction factor
+    pub reduction_factor: u16,
+    // Variable fee control
+    pub variable_fee_control: u32,
+    // Max volatility accumulator
+    pub max_volatility_accumulator: u32,
+    // Protocol share in BPS
+    pub protocol_share: u16,
+}
+
+#[event]
+pub struct PresetParameterSync {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Preset parameter public key
+    pub preset_parameter: Pubkey,
+    // Version synced to
+    pub version: u64,
+}
+
+#[event]
+pub struct CloseLbPair {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Pool creator receiving the rental SOL
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct ClaimAll {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Owner of the positions
+    pub owner: Pubkey,
+    // Number of positions claimed
+    pub position_count: u64,
+    // Total fee amount in token X
+    pub fee_x: u64,
+    // Total fee amount in token Y
+    pub fee_y: u64,
+    // Total amount of each farm reward claimed
+    pub rewards: [u64; 2],
+}
+
+#[event]
+pub struct ProtocolShareUpdate {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Protocol share in BPS
+    pub protocol_share: u16,
+    // Protocol share in BPS after the scheduled step down
+    pub target_protocol_share: u16,
+    // Point where the protocol share steps down. 0 if not scheduled
+    pub step_down_point: u64,
+}
+
+#[event]
+pub struct FeeTierUpdate {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Fee tier address
+    pub fee_tier: Pubkey,
+    // Wallet granted the discount
+    pub wallet: Pubkey,
+    // Swap fee discount in BPS
+    pub discount_bps: u16,
+}
+
+#[event]
+pub struct MigrateLiquidity {
+    // Source liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Source position
+    pub position: Pubkey,
+    // Destination liquidity pool pair
+    pub dest_lb_pair: Pubkey,
+    // Destination position
+    pub dest_position: Pubkey,
+    // Amount of token X, and Y withdrawn from the source position
+    pub amounts: [u64; 2],
+    // Amount of token X, and Y deposited into the destination position, including composition fee
+    pub dest_amounts: [u64; 2],
+}
+
+#[event]
+pub struct PairBootstrap {
+    // Liquidity pool pair
+    pub lb_pair: Pubkey,
+    // Position seeded with the launch liquidity
+    pub position: Pubkey,
+    // Owner of the position
+    pub owner: Pubkey,
+    // Active bin of the pair
+    pub active_id: i32,
+    // Amount of token X deposited
+    pub amount_x: u64,
+}
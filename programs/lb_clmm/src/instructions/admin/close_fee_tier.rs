@@ -0,0 +1,28 @@
+use crate::assert_eq_admin;
+use crate::errors::LBError;
+use crate::state::fee_tier::FeeTier;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseFeeTier<'info> {
+    #[account(
+        mut,
+        close = rent_receiver
+    )]
+    pub fee_tier: AccountLoader<'info, FeeTier>,
+
+    #[account(
+        mut,
+        constraint = assert_eq_admin(admin.key()) @ LBError::InvalidAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Account to receive closed account rental SOL
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle(_ctx: Context<CloseFeeTier>) -> Result<()> {
+    // Anchor handle everything
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::assert_eq_admin;
+use crate::errors::LBError;
+use crate::events::CloseOracle as CloseOracleEvent;
+use crate::state::lb_pair::{LbPair, PairStatus};
+use crate::state::oracle::Oracle;
+use crate::utils::seeds::ORACLE;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseOracle<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ORACLE,
+            lb_pair.key().as_ref()
+        ],
+        bump,
+        close = rent_receiver
+    )]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    /// CHECK: Pair of the oracle. Validated in the handler as it can be a closed account.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(constraint = assert_eq_admin(admin.key()) @ LBError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Account to receive closed account rental SOL
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Oracle can only be closed when the pair was closed, or disabled.
+pub fn handle(ctx: Context<CloseOracle>) -> Result<()> {
+    let lb_pair_info = ctx.accounts.lb_pair.to_account_info();
+
+    let is_pair_closed = lb_pair_info.owner != &crate::ID || lb_pair_info.data_is_empty();
+    if !is_pair_closed {
+        let lb_pair_loader = AccountLoader::<LbPair>::try_from(&lb_pair_info)?;
+        let lb_pair = lb_pair_loader.load()?;
+        require!(
+            lb_pair.status()? == PairStatus::Disabled,
+            LBError::PairNotClosedOrDisabled
+        );
+    }
+
+    emit_cpi!(CloseOracleEvent {
+        oracle: ctx.accounts.oracle.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::assert_eq_admin;
+use crate::errors::LBError;
+use crate::events::DecreaseObservation;
+use crate::math::safe_math::SafeMath;
+use crate::state::oracle::{Oracle, OracleContentLoader};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DecreaseOracleLength<'info> {
+    #[account(mut)]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    #[account(constraint = assert_eq_admin(admin.key()) @ LBError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Account to receive released rental SOL
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle(ctx: Context<DecreaseOracleLength>, length_to_remove: u64) -> Result<()> {
+    let new_observation_length = {
+        let mut dynamic_oracle = ctx.accounts.oracle.load_content_mut()?;
+        dynamic_oracle.decrease_length(length_to_remove)?;
+        dynamic_oracle.metadata.length
+    };
+
+    // Observations were compacted to the front, the tail can be dropped
+    let oracle_info = ctx.accounts.oracle.to_account_info();
+    let space = Oracle::space(new_observation_length);
+    oracle_info.realloc(space, false)?;
+
+    let excess_lamports = oracle_info
+        .lamports()
+        .safe_sub(Rent::get()?.minimum_balance(space))?;
+
+    if excess_lamports > 0 {
+        let rent_receiver_info = ctx.accounts.rent_receiver.to_account_info();
+        **oracle_info.try_borrow_mut_lamports()? =
+            oracle_info.lamports().safe_sub(excess_lamports)?;
+        **rent_receiver_info.try_borrow_mut_lamports()? =
+            rent_receiver_info.lamports().safe_add(excess_lamports)?;
+    }
+
+    emit_cpi!(DecreaseObservation {
+        oracle: ctx.accounts.oracle.key(),
+        new_observation_length,
+    });
+
+    Ok(())
+}
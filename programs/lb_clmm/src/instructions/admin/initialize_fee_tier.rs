@@ -0,0 +1,55 @@
+use crate::assert_eq_admin;
+use crate::errors::LBError;
+use crate::events::FeeTierUpdate;
+use crate::state::fee_tier::FeeTier;
+use crate::state::lb_pair::LbPair;
+use crate::utils::seeds::FEE_TIER;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeFeeTier<'info> {
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        init,
+        seeds = [
+            FEE_TIER,
+            lb_pair.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump,
+        payer = admin,
+        space = 8 + FeeTier::INIT_SPACE
+    )]
+    pub fee_tier: AccountLoader<'info, FeeTier>,
+
+    /// CHECK: Wallet granted the discounted swap fee
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = assert_eq_admin(admin.key()) @ LBError::InvalidAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<InitializeFeeTier>, discount_bps: u16) -> Result<()> {
+    let mut fee_tier = ctx.accounts.fee_tier.load_init()?;
+    fee_tier.init(
+        ctx.accounts.lb_pair.key(),
+        ctx.accounts.wallet.key(),
+        discount_bps,
+    )?;
+
+    emit_cpi!(FeeTierUpdate {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        fee_tier: ctx.accounts.fee_tier.key(),
+        wallet: ctx.accounts.wallet.key(),
+        discount_bps,
+    });
+
+    Ok(())
+}
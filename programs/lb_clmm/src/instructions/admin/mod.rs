@@ -1,21 +1,37 @@
+pub mod close_fee_tier;
+pub mod close_oracle;
 pub mod close_preset_parameter;
+pub mod decrease_oracle_length;
+pub mod initialize_fee_tier;
 pub mod initialize_preset_parameters;
 pub mod initialize_reward;
 pub mod set_activation_point;
 pub mod set_pair_status;
 pub mod set_pre_activation_duration;
 pub mod set_pre_activation_swap_address;
+pub mod set_protocol_share;
+pub mod set_whitelist_merkle_root;
 pub mod update_fee_parameters;
+pub mod update_fee_tier;
+pub mod update_preset_parameter;
 pub mod update_reward_duration;
 pub mod update_reward_funder;
 
+pub use close_fee_tier::*;
+pub use close_oracle::*;
 pub use close_preset_parameter::*;
+pub use decrease_oracle_length::*;
+pub use initialize_fee_tier::*;
 pub use initialize_preset_parameters::*;
 pub use initialize_reward::*;
 pub use set_activation_point::*;
 pub use set_pair_status::*;
 pub use set_pre_activation_duration::*;
 pub use set_pre_activation_swap_address::*;
+pub use set_protocol_share::*;
+pub use set_whitelist_merkle_root::*;
 pub use update_fee_parameters::*;
+pub use update_fee_tier::*;
+pub use update_preset_parameter::*;
 pub use update_reward_duration::*;
 pub use update_reward_funder::*;
@@ -0,0 +1,66 @@
+use crate::assert_eq_admin;
+use crate::errors::LBError;
+use crate::events::ProtocolShareUpdate;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::lb_pair::{LbPair, PairType};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProtocolShareStepDown {
+    /// Protocol share in bps after the step down. Must be lower than the current protocol share.
+    pub target_protocol_share: u16,
+    /// Slot or timestamp, based on the activation type of the pair.
+    pub step_down_point: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProtocolShareParameter {
+    /// Portion of swap fees retained by the protocol in bps
+    pub protocol_share: u16,
+    /// Scheduled step down. Only applicable for customizable permissionless pair.
+    pub step_down: Option<ProtocolShareStepDown>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetProtocolShare<'info> {
+    #[account(mut)]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(constraint = assert_eq_admin(admin.key()) @ LBError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handle(ctx: Context<SetProtocolShare>, ix: ProtocolShareParameter) -> Result<()> {
+    let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+
+    let step_down = match &ix.step_down {
+        Some(step_down) => {
+            require!(
+                lb_pair.pair_type()? == PairType::CustomizablePermissionless,
+                LBError::InvalidPoolType
+            );
+
+            let current_point = get_lb_pair_type_access_validator(&lb_pair)?.get_current_point();
+            require!(
+                step_down.target_protocol_share < ix.protocol_share
+                    && step_down.step_down_point > current_point,
+                LBError::InvalidProtocolShareStepDown
+            );
+
+            Some((step_down.target_protocol_share, step_down.step_down_point))
+        }
+        None => None,
+    };
+
+    lb_pair.set_protocol_share(ix.protocol_share, step_down)?;
+
+    emit_cpi!(ProtocolShareUpdate {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        protocol_share: lb_pair.parameters.protocol_share,
+        target_protocol_share: lb_pair.parameters.target_protocol_share,
+        step_down_point: lb_pair.protocol_share_step_down_point,
+    });
+
+    Ok(())
+}
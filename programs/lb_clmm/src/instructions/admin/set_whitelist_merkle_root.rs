@@ -0,0 +1,26 @@
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::lb_pair::LbPair;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetWhitelistMerkleRoot<'info> {
+    #[account(
+        mut,
+        has_one = creator
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handle(ctx: Context<SetWhitelistMerkleRoot>, whitelist_merkle_root: [u8; 32]) -> Result<()> {
+    let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+
+    let pair_type_access_validator = get_lb_pair_type_access_validator(&lb_pair)?;
+    pair_type_access_validator.validate_set_whitelist_merkle_root()?;
+    drop(pair_type_access_validator);
+
+    lb_pair.set_whitelist_merkle_root(whitelist_merkle_root);
+
+    Ok(())
+}
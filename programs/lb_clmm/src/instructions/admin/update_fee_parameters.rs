@@ -1,5 +1,6 @@
 use crate::assert_eq_admin;
 use crate::errors::LBError;
+use crate::events::FeeParameterUpdate;
 use crate::state::lb_pair::LbPair;
 use anchor_lang::prelude::*;
 
@@ -9,6 +10,8 @@ pub struct FeeParameter {
     pub protocol_share: u16,
     /// Base factor for base fee rate
     pub base_factor: u16,
+    /// Extra fee rate in bps charged on swap which reverses the direction of earlier swaps within the same slot. 0 to disable.
+    pub volatility_surcharge_bps: u16,
 }
 
 #[event_cpi]
@@ -22,5 +25,15 @@ pub struct UpdateFeeParameters<'info> {
 }
 
 pub fn handle(ctx: Context<UpdateFeeParameters>, fee_parameter: FeeParameter) -> Result<()> {
+    let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+    lb_pair.update_fee_parameters(&fee_parameter)?;
+
+    emit_cpi!(FeeParameterUpdate {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        protocol_share: fee_parameter.protocol_share,
+        base_factor: fee_parameter.base_factor,
+        volatility_surcharge_bps: fee_parameter.volatility_surcharge_bps,
+    });
+
     Ok(())
 }
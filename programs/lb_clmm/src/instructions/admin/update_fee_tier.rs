@@ -0,0 +1,29 @@
+use crate::assert_eq_admin;
+use crate::errors::LBError;
+use crate::events::FeeTierUpdate;
+use crate::state::fee_tier::FeeTier;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFeeTier<'info> {
+    #[account(mut)]
+    pub fee_tier: AccountLoader<'info, FeeTier>,
+
+    #[account(constraint = assert_eq_admin(admin.key()) @ LBError::InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handle(ctx: Context<UpdateFeeTier>, discount_bps: u16) -> Result<()> {
+    let mut fee_tier = ctx.accounts.fee_tier.load_mut()?;
+    fee_tier.update(discount_bps)?;
+
+    emit_cpi!(FeeTierUpdate {
+        lb_pair: fee_tier.lb_pair,
+        fee_tier: ctx.accounts.fee_tier.key(),
+        wallet: fee_tier.wallet,
+        discount_bps,
+    });
+
+    Ok(())
+}
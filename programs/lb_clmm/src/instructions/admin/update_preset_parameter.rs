@@ -0,0 +1,73 @@
+use crate::assert_eq_admin;
+use crate::errors::LBError;
+use crate::events::PresetParameterUpdate;
+use crate::state::preset_parameters::PresetParameter;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdatePresetParametersIx {
+    /// Filter period determine high frequency trading time window.
+    pub filter_period: u16,
+    /// Decay period determine when the volatile fee start decay / decrease.
+    pub decay_period: u16,
+    /// Reduction factor controls the volatile fee rate decrement rate.
+    pub reduction_factor: u16,
+    /// Used to scale the variable fee component depending on the dynamic of the market
+    pub variable_fee_control: u32,
+    /// Maximum number of bin crossed can be accumulated. Used to cap volatile fee rate.
+    pub max_volatility_accumulator: u32,
+    /// Portion of swap fees retained by the protocol by controlling protocol_share parameter. protocol_swap_fee = protocol_share * total_swap_fee
+    pub protocol_share: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePresetParameter<'info> {
+    /// Preset parameter created before the padding was introduced is reallocated to hold the version
+    #[account(
+        mut,
+        realloc = 8 + PresetParameter::INIT_SPACE,
+        realloc::payer = admin,
+        realloc::zero = false,
+    )]
+    pub preset_parameter: Account<'info, PresetParameter>,
+
+    #[account(
+        mut,
+        constraint = assert_eq_admin(admin.key()) @ LBError::InvalidAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Bin step, and base factor are part of the preset parameter seeds, therefore not updatable.
+pub fn handle(ctx: Context<UpdatePresetParameter>, ix: UpdatePresetParametersIx) -> Result<()> {
+    let preset_parameter = &mut ctx.accounts.preset_parameter;
+
+    let base_factor = preset_parameter.base_factor;
+    preset_parameter.update(
+        base_factor,
+        ix.filter_period,
+        ix.decay_period,
+        ix.reduction_factor,
+        ix.variable_fee_control,
+        ix.max_volatility_accumulator,
+        ix.protocol_share,
+    );
+    preset_parameter.validate()?;
+    preset_parameter.bump_version()?;
+
+    emit_cpi!(PresetParameterUpdate {
+        preset_parameter: preset_parameter.key(),
+        version: preset_parameter.version(),
+        filter_period: ix.filter_period,
+        decay_period: ix.decay_period,
+        reduction_factor: ix.reduction_factor,
+        variable_fee_control: ix.variable_fee_control,
+        max_volatility_accumulator: ix.max_volatility_accumulator,
+        protocol_share: ix.protocol_share,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use crate::{authorize_claim_fee_position, authorize_modify_position};
+use crate::constants::NUM_REWARDS;
+use crate::errors::LBError;
+use crate::events::ClaimAll as ClaimAllEvent;
+use crate::manager::bin_array_manager::BinArrayManager;
+use crate::math::safe_math::SafeMath;
+use crate::state::{bin::BinArray, lb_pair::LbPair, position::PositionV2};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Number of remaining accounts required by each position. [position, bin_array_lower, bin_array_upper]
+pub const CLAIM_ALL_ACCOUNTS_PER_POSITION: usize = 3;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimAll<'info> {
+    #[account(
+        mut,
+        has_one = reserve_x,
+        has_one = reserve_y,
+        has_one = token_x_mint,
+        has_one = token_y_mint,
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    pub sender: Signer<'info>,
+
+    #[account(mut)]
+    pub reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user_token_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub user_token_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_x_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_y_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Reward accounts are optional. Reward without accounts provided remain pending in the positions.
+    #[account(mut)]
+    pub reward_vault_0: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub reward_mint_0: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub user_token_reward_0: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub reward_token_program_0: Option<Interface<'info, TokenInterface>>,
+
+    #[account(mut)]
+    pub reward_vault_1: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub reward_mint_1: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub user_token_reward_1: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub reward_token_program_1: Option<Interface<'info, TokenInterface>>,
+}
+
+struct RewardAccounts<'a, 'info> {
+    vault: &'a InterfaceAccount<'info, TokenAccount>,
+    mint: &'a InterfaceAccount<'info, Mint>,
+    user_token: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimAll<'info> {
+    fn get_reward_accounts<'a>(&'a self, reward_index: usize) -> Option<RewardAccounts<'a, 'info>> {
+        let (vault, mint, user_token, token_program) = if reward_index == 0 {
+            (
+                self.reward_vault_0.as_ref()?,
+                self.reward_mint_0.as_ref()?,
+                self.user_token_reward_0.as_ref()?,
+                self.reward_token_program_0.as_ref()?,
+            )
+        } else {
+            (
+                self.reward_vault_1.as_ref()?,
+                self.reward_mint_1.as_ref()?,
+                self.user_token_reward_1.as_ref()?,
+                self.reward_token_program_1.as_ref()?,
+            )
+        };
+
+        Some(RewardAccounts {
+            vault,
+            mint,
+            user_token,
+            token_program,
+        })
+    }
+
+    fn transfer_from_lb_pair(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program,
+                TransferChecked {
+                    from,
+                    to,
+                    authority: self.lb_pair.to_account_info(),
+                    mint: mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+}
+
+/// Claim fees, and rewards of positions passed through remaining accounts. All positions must belong to the same owner and pair. Claimed amount is transferred once per token.
+pub fn handle<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, ClaimAll<'info>>) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty()
+            && remaining_accounts.len() % CLAIM_ALL_ACCOUNTS_PER_POSITION == 0,
+        LBError::InvalidInput
+    );
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let sender = ctx.accounts.sender.key();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let claim_rewards: Vec<bool> = {
+        let lb_pair = ctx.accounts.lb_pair.load()?;
+        (0..NUM_REWARDS)
+            .map(|reward_index| {
+                let reward_info = &lb_pair.reward_infos[reward_index];
+                match ctx.accounts.get_reward_accounts(reward_index) {
+                    Some(reward_accounts) => {
+                        require!(reward_info.initialized(), LBError::RewardUninitialized);
+                        require!(
+                            reward_accounts.vault.key() == reward_info.vault,
+                            LBError::InvalidRewardVault
+                        );
+                        require!(
+                            reward_accounts.mint.key() == reward_info.mint,
+                            LBError::InvalidRewardVault
+                        );
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            })
+            .collect::<Result<Vec<bool>>>()?
+    };
+
+    let mut owner: Option<Pubkey> = None;
+    let mut total_fee_x = 0u64;
+    let mut total_fee_y = 0u64;
+    let mut total_rewards = [0u64; NUM_REWARDS];
+
+    for accounts in remaining_accounts.chunks(CLAIM_ALL_ACCOUNTS_PER_POSITION) {
+        let position_loader = AccountLoader::<PositionV2>::try_from(&accounts[0])?;
+        let bin_array_lower = AccountLoader::<BinArray>::try_from(&accounts[1])?;
+        let bin_array_upper = AccountLoader::<BinArray>::try_from(&accounts[2])?;
+
+        require!(
+            authorize_claim_fee_position(&position_loader, sender)?,
+            LBError::UnauthorizedAccess
+        );
+        // Fee owner can only claim the fees
+        if claim_rewards.iter().any(|claim_reward| *claim_reward) {
+            require!(
+                authorize_modify_position(&position_loader, sender)?,
+                LBError::UnauthorizedAccess
+            );
+        }
+
+        let mut position = position_loader.load_mut()?;
+        require!(position.lb_pair == lb_pair_key, LBError::InvalidPosition);
+
+        let position_owner = *owner.get_or_insert(position.owner);
+        require!(position.owner == position_owner, LBError::InvalidPosition);
+
+        let mut bin_arrays = [bin_array_lower.load_mut()?, bin_array_upper.load_mut()?];
+        for bin_array in bin_arrays.iter() {
+            require!(bin_array.lb_pair == lb_pair_key, LBError::InvalidBinArray);
+        }
+
+        let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays)?;
+        bin_array_manager.validate_bin_arrays(position.lower_bin_id)?;
+
+        {
+            let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+            bin_array_manager.update_rewards(&mut lb_pair)?;
+        }
+
+        position.update_earning_per_token_stored(&bin_array_manager)?;
+
+        let (fee_x, fee_y) = position.claim_fee()?;
+        position.accumulate_total_claimed_fees(fee_x, fee_y);
+        total_fee_x = total_fee_x.safe_add(fee_x)?;
+        total_fee_y = total_fee_y.safe_add(fee_y)?;
+
+        for (reward_index, claim_reward) in claim_rewards.iter().enumerate() {
+            if !claim_reward {
+                continue;
+            }
+            let total_reward = position.get_total_reward(reward_index)?;
+            position.reset_all_pending_reward(reward_index);
+            position.accumulate_total_claimed_rewards(reward_index, total_reward);
+            total_rewards[reward_index] = total_rewards[reward_index].safe_add(total_reward)?;
+        }
+
+        position.set_last_updated_at(current_time);
+    }
+
+    let lb_pair_seeds = ctx.accounts.lb_pair.load()?.signer_seeds()?;
+    let lb_pair_seeds = lb_pair_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect::<Vec<&[u8]>>();
+    let signer_seeds = &[&lb_pair_seeds[..]];
+
+    ctx.accounts.transfer_from_lb_pair(
+        ctx.accounts.reserve_x.to_account_info(),
+        ctx.accounts.user_token_x.to_account_info(),
+        &ctx.accounts.token_x_mint,
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+        total_fee_x,
+    )?;
+    ctx.accounts.transfer_from_lb_pair(
+        ctx.accounts.reserve_y.to_account_info(),
+        ctx.accounts.user_token_y.to_account_info(),
+        &ctx.accounts.token_y_mint,
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+        total_fee_y,
+    )?;
+
+    for (reward_index, total_reward) in total_rewards.iter().enumerate() {
+        if let Some(reward_accounts) = ctx.accounts.get_reward_accounts(reward_index) {
+            ctx.accounts.transfer_from_lb_pair(
+                reward_accounts.vault.to_account_info(),
+                reward_accounts.user_token.to_account_info(),
+                reward_accounts.mint,
+                reward_accounts.token_program.to_account_info(),
+                signer_seeds,
+                *total_reward,
+            )?;
+        }
+    }
+
+    emit_cpi!(ClaimAllEvent {
+        lb_pair: lb_pair_key,
+        owner: owner.unwrap_or_default(),
+        position_count: (remaining_accounts.len() / CLAIM_ALL_ACCOUNTS_PER_POSITION) as u64,
+        fee_x: total_fee_x,
+        fee_y: total_fee_y,
+        rewards: total_rewards,
+    });
+
+    Ok(())
+}
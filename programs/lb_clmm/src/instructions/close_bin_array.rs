@@ -0,0 +1,58 @@
+use crate::errors::LBError;
+use crate::instructions::close_lb_pair::close_program_account;
+use crate::state::bin::BinArray;
+use crate::state::lb_pair::LbPair;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct CloseBinArray<'info> {
+    #[account(
+        mut,
+        has_one = reserve_x,
+        has_one = reserve_y,
+        has_one = creator @ LBError::WrongRentReceiver,
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    pub reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Pool creator. Receive the rental SOL of the closed bin arrays.
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+}
+
+/// Permissionless. Close the bin arrays passed through remaining accounts of a pair without any position, liquidity, protocol fee and farming reward. The pair can be closed once all of its bin arrays are closed.
+pub fn handle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CloseBinArray<'info>>,
+) -> Result<()> {
+    let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+    lb_pair.validate_empty()?;
+
+    require!(
+        ctx.accounts.reserve_x.amount == 0 && ctx.accounts.reserve_y.amount == 0,
+        LBError::PairNotEmpty
+    );
+    require!(!ctx.remaining_accounts.is_empty(), LBError::InvalidInput);
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let creator = ctx.accounts.creator.to_account_info();
+
+    for bin_array_info in ctx.remaining_accounts.iter() {
+        {
+            // Fail on duplicated account as the owner is reassigned once closed
+            let bin_array_loader = AccountLoader::<BinArray>::try_from(bin_array_info)?;
+            let bin_array = bin_array_loader.load()?;
+            require!(bin_array.lb_pair == lb_pair_key, LBError::InvalidBinArray);
+            require!(bin_array.is_zero_liquidity(), LBError::PairNotEmpty);
+        }
+        close_program_account(bin_array_info, &creator)?;
+    }
+
+    let closed_count =
+        u16::try_from(ctx.remaining_accounts.len()).map_err(|_| LBError::TypeCastFailed)?;
+    lb_pair.decrease_bin_array_count(closed_count);
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::errors::LBError;
+use crate::events::CloseLbPair as CloseLbPairEvent;
+use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
+use crate::state::lb_pair::LbPair;
+use crate::utils::seeds::{BIN_ARRAY_BITMAP_SEED, ORACLE};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, CloseAccount, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseLbPair<'info> {
+    #[account(
+        mut,
+        has_one = reserve_x,
+        has_one = reserve_y,
+        has_one = creator @ LBError::WrongRentReceiver,
+        close = creator
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(mut)]
+    pub reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Oracle of the pair. Might be closed by close_oracle.
+    #[account(
+        mut,
+        seeds = [
+            ORACLE,
+            lb_pair.key().as_ref()
+        ],
+        bump,
+    )]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: Bin array bitmap extension of the pair. Might not be initialized.
+    #[account(
+        mut,
+        seeds = [
+            BIN_ARRAY_BITMAP_SEED,
+            lb_pair.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bin_array_bitmap_extension: UncheckedAccount<'info>,
+
+    /// CHECK: Pool creator. Receive the rental SOL of all closed accounts.
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub token_x_program: Interface<'info, TokenInterface>,
+    pub token_y_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CloseLbPair<'info> {
+    fn close_reserve(
+        &self,
+        reserve: AccountInfo<'info>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        close_account(CpiContext::new_with_signer(
+            token_program,
+            CloseAccount {
+                account: reserve,
+                destination: self.creator.to_account_info(),
+                authority: self.lb_pair.to_account_info(),
+            },
+            signer_seeds,
+        ))
+    }
+}
+
+/// Transfer out the rental SOL and return the account to the system program
+pub fn close_program_account<'info>(
+    info: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = info.lamports();
+    **rent_receiver.try_borrow_mut_lamports()? = rent_receiver
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(LBError::MathOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+
+    info.assign(&System::id());
+    info.realloc(0, false)?;
+
+    Ok(())
+}
+
+/// Permissionless. Retire a pair without any position, liquidity, protocol fee and farming reward. All rental SOL goes to the pool creator.
+/// Bin arrays must be closed beforehand by close_bin_array. Bin arrays of pairs created before the counters were introduced are not tracked, any left behind is reused by a pair re-created at the same address.
+pub fn handle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CloseLbPair<'info>>,
+) -> Result<()> {
+    let lb_pair_seeds = {
+        let lb_pair = ctx.accounts.lb_pair.load()?;
+        lb_pair.validate_empty()?;
+
+        if lb_pair.is_account_tracking_enabled() {
+            require!(lb_pair.bin_array_count == 0, LBError::PairNotEmpty);
+        }
+
+        lb_pair.signer_seeds()?
+    };
+
+    require!(
+        ctx.accounts.reserve_x.amount == 0 && ctx.accounts.reserve_y.amount == 0,
+        LBError::PairNotEmpty
+    );
+
+    let creator = ctx.accounts.creator.to_account_info();
+
+    let bin_array_bitmap_extension = ctx.accounts.bin_array_bitmap_extension.to_account_info();
+    if bin_array_bitmap_extension.owner == &crate::ID {
+        {
+            let bitmap_extension_loader =
+                AccountLoader::<BinArrayBitmapExtension>::try_from(&bin_array_bitmap_extension)?;
+            require!(
+                bitmap_extension_loader.load()?.is_empty(),
+                LBError::PairNotEmpty
+            );
+        }
+        close_program_account(&bin_array_bitmap_extension, &creator)?;
+    }
+
+    let oracle = ctx.accounts.oracle.to_account_info();
+    if oracle.owner == &crate::ID {
+        close_program_account(&oracle, &creator)?;
+    }
+
+    let lb_pair_seeds = lb_pair_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect::<Vec<&[u8]>>();
+    let signer_seeds = &[&lb_pair_seeds[..]];
+
+    ctx.accounts.close_reserve(
+        ctx.accounts.reserve_x.to_account_info(),
+        ctx.accounts.token_x_program.to_account_info(),
+        signer_seeds,
+    )?;
+    ctx.accounts.close_reserve(
+        ctx.accounts.reserve_y.to_account_info(),
+        ctx.accounts.token_y_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    emit_cpi!(CloseLbPairEvent {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        creator: ctx.accounts.creator.key(),
+    });
+
+    // Anchor close the pair
+    Ok(())
+}
@@ -36,5 +36,7 @@ pub struct ClosePosition<'info> {
 }
 
 pub fn handle(ctx: Context<ClosePosition>) -> Result<()> {
+    ctx.accounts.lb_pair.load_mut()?.decrease_position_count();
+
     Ok(())
 }
@@ -15,6 +15,7 @@ pub struct InitializePosition<'info> {
     )]
     pub position: AccountLoader<'info, PositionV2>,
 
+    #[account(mut)]
     pub lb_pair: AccountLoader<'info, LbPair>,
 
     pub owner: Signer<'info>,
@@ -24,5 +25,7 @@ pub struct InitializePosition<'info> {
 }
 
 pub fn handle(ctx: Context<InitializePosition>, lower_bin_id: i32, width: i32) -> Result<()> {
+    ctx.accounts.lb_pair.load_mut()?.increase_position_count()?;
+
     Ok(())
 }
@@ -27,6 +27,7 @@ pub struct InitializePositionByOperator<'info> {
     )]
     pub position: AccountLoader<'info, PositionV2>,
 
+    #[account(mut)]
     pub lb_pair: AccountLoader<'info, LbPair>,
 
     /// CHECK: owner of position
@@ -57,5 +58,7 @@ pub fn handle(
     fee_owner: Pubkey,
     lock_release_point: u64,
 ) -> Result<()> {
+    ctx.accounts.lb_pair.load_mut()?.increase_position_count()?;
+
     Ok(())
 }
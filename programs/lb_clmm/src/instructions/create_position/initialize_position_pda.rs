@@ -26,6 +26,7 @@ pub struct InitializePositionPda<'info> {
     )]
     pub position: AccountLoader<'info, PositionV2>,
 
+    #[account(mut)]
     pub lb_pair: AccountLoader<'info, LbPair>,
 
     /// owner
@@ -37,5 +38,7 @@ pub struct InitializePositionPda<'info> {
 }
 
 pub fn handle(ctx: Context<InitializePositionPda>, lower_bin_id: i32, width: i32) -> Result<()> {
+    ctx.accounts.lb_pair.load_mut()?.increase_position_count()?;
+
     Ok(())
 }
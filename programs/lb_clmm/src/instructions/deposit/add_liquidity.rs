@@ -1,9 +1,18 @@
 use crate::authorize_modify_position;
+use crate::instructions::deposit::validate_add_liquidity_access;
+use crate::manager::bin_array_manager::BinArrayManager;
+use crate::math::bin_math::get_liquidity;
+use crate::math::safe_math::SafeMath;
 use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
 use crate::state::position::PositionV2;
-use crate::state::{bin::BinArray, lb_pair::LbPair};
+use crate::state::{
+    bin::{get_liquidity_share, Bin, BinArray},
+    lb_pair::LbPair,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use num_traits::Zero;
+use std::cell::RefMut;
 
 pub struct CompositeDepositInfo {
     pub liquidity_share: u128,
@@ -91,9 +100,167 @@ pub struct ModifyLiquidity<'info> {
     pub token_y_program: Interface<'info, TokenInterface>,
 }
 
+/// Composition fee is charged when the deposit into the active bin changes the bin composition. The fee is charged on the portion which is equivalent to a swap.
+pub fn get_composition_fee(
+    lb_pair: &LbPair,
+    bin: &Bin,
+    liquidity_share: u128,
+    amount_x: u64,
+    amount_y: u64,
+) -> Result<(u64, u64)> {
+    if bin.is_zero_liquidity() {
+        return Ok((0, 0));
+    }
+
+    let mut bin_after_deposit = *bin;
+    bin_after_deposit.deposit(amount_x, amount_y, liquidity_share)?;
+    let (out_amount_x, out_amount_y) = bin_after_deposit.calculate_out_amount(liquidity_share)?;
+
+    if amount_x > out_amount_x && amount_y < out_amount_y {
+        let fee_x = lb_pair.compute_composition_fee(amount_x.safe_sub(out_amount_x)?)?;
+        Ok((fee_x, 0))
+    } else if amount_y > out_amount_y && amount_x < out_amount_x {
+        let fee_y = lb_pair.compute_composition_fee(amount_y.safe_sub(out_amount_y)?)?;
+        Ok((0, fee_y))
+    } else {
+        Ok((0, 0))
+    }
+}
+
+fn compute_liquidity_share(bin: &Bin, amount_x: u64, amount_y: u64, price: u128) -> Result<u128> {
+    let in_liquidity = get_liquidity(amount_x, amount_y, price)?;
+    if bin.is_zero_liquidity() {
+        return Ok(in_liquidity);
+    }
+    let bin_liquidity = get_liquidity(bin.amount_x, bin.amount_y, price)?;
+    get_liquidity_share(in_liquidity, bin_liquidity, bin.liquidity_supply)
+}
+
+/// Liquidity share minted for the deposit into the bin. Composition fee of the active bin, excluding the protocol fee, is deposited into the bin before the share is computed.
+fn get_composite_deposit_info(
+    lb_pair: &LbPair,
+    bin: &mut Bin,
+    bin_id: i32,
+    amount_x: u64,
+    amount_y: u64,
+) -> Result<(CompositeDepositInfo, u64, u64)> {
+    let price = bin.get_or_store_bin_price(bin_id, lb_pair.bin_step)?;
+    let liquidity_share = compute_liquidity_share(bin, amount_x, amount_y, price)?;
+
+    if bin_id != lb_pair.active_id {
+        let deposit_info = CompositeDepositInfo {
+            liquidity_share,
+            protocol_token_x_fee_amount: 0,
+            protocol_token_y_fee_amount: 0,
+        };
+        return Ok((deposit_info, amount_x, amount_y));
+    }
+
+    let (fee_x, fee_y) = get_composition_fee(lb_pair, bin, liquidity_share, amount_x, amount_y)?;
+    let protocol_fee_x = lb_pair.compute_protocol_fee(fee_x)?;
+    let protocol_fee_y = lb_pair.compute_protocol_fee(fee_y)?;
+
+    bin.deposit_composition_fee(
+        fee_x.safe_sub(protocol_fee_x)?,
+        fee_y.safe_sub(protocol_fee_y)?,
+    )?;
+
+    let amount_x_into_bin = amount_x.safe_sub(fee_x)?;
+    let amount_y_into_bin = amount_y.safe_sub(fee_y)?;
+
+    let deposit_info = CompositeDepositInfo {
+        liquidity_share: compute_liquidity_share(bin, amount_x_into_bin, amount_y_into_bin, price)?,
+        protocol_token_x_fee_amount: protocol_fee_x,
+        protocol_token_y_fee_amount: protocol_fee_y,
+    };
+
+    Ok((deposit_info, amount_x_into_bin, amount_y_into_bin))
+}
+
+/// Deposit the amounts into the bins of the position. Fees, and rewards are settled into the position before the liquidity share changes. Bin arrays become non-empty by the deposit are flipped on the bin array bitmap.
+/// Return the total amount of token X, and Y consumed, including composition fee. Bin which the deposit mint no liquidity share is skipped.
+pub fn deposit_position_liquidity<'info>(
+    lb_pair: &mut RefMut<'_, LbPair>,
+    position: &mut PositionV2,
+    bin_array_manager: &mut BinArrayManager,
+    bin_array_bitmap_extension: &Option<AccountLoader<'info, BinArrayBitmapExtension>>,
+    amounts_into_bin: &[(i32, u64, u64)],
+) -> Result<(u64, u64)> {
+    bin_array_manager.validate_bin_arrays(position.lower_bin_id)?;
+    bin_array_manager.update_rewards(lb_pair)?;
+    position.update_earning_per_token_stored(bin_array_manager)?;
+
+    lb_pair.update_volatility_parameters(Clock::get()?.unix_timestamp)?;
+
+    let before_zero_liquidity_flags = bin_array_manager.get_zero_liquidity_flags();
+    let mut total_amount_x: u64 = 0;
+    let mut total_amount_y: u64 = 0;
+
+    for &(bin_id, amount_x, amount_y) in amounts_into_bin.iter() {
+        if amount_x == 0 && amount_y == 0 {
+            continue;
+        }
+
+        let bin = bin_array_manager.get_bin_mut(bin_id)?;
+        let mut bin_after_deposit = *bin;
+        let (deposit_info, amount_x_into_bin, amount_y_into_bin) = get_composite_deposit_info(
+            lb_pair,
+            &mut bin_after_deposit,
+            bin_id,
+            amount_x,
+            amount_y,
+        )?;
+
+        if deposit_info.liquidity_share.is_zero() {
+            continue;
+        }
+
+        bin_after_deposit.deposit(
+            amount_x_into_bin,
+            amount_y_into_bin,
+            deposit_info.liquidity_share,
+        )?;
+        *bin = bin_after_deposit;
+
+        position.deposit(bin_id, deposit_info.liquidity_share)?;
+        lb_pair.accumulate_protocol_fees(
+            deposit_info.protocol_token_x_fee_amount,
+            deposit_info.protocol_token_y_fee_amount,
+        )?;
+
+        total_amount_x = total_amount_x.safe_add(amount_x)?;
+        total_amount_y = total_amount_y.safe_add(amount_y)?;
+    }
+
+    let after_zero_liquidity_flags = bin_array_manager.get_zero_liquidity_flags();
+    for (i, (before, after)) in before_zero_liquidity_flags
+        .iter()
+        .zip(after_zero_liquidity_flags.iter())
+        .enumerate()
+    {
+        if *before && !after {
+            lb_pair.flip_bin_array_bit(
+                bin_array_bitmap_extension,
+                bin_array_manager.get_bin_array_index(i)?,
+            )?;
+        }
+    }
+
+    Ok((total_amount_x, total_amount_y))
+}
+
 pub fn handle<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
     liquidity_parameter: LiquidityParameter,
 ) -> Result<()> {
+    let mut remaining_accounts = ctx.remaining_accounts;
+    validate_add_liquidity_access(
+        &ctx.accounts.lb_pair,
+        ctx.accounts.sender.key(),
+        &mut remaining_accounts,
+        liquidity_parameter.amount_x,
+        liquidity_parameter.amount_y,
+    )?;
+
     Ok(())
 }
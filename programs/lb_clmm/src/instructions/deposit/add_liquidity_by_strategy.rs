@@ -1,5 +1,6 @@
 use crate::constants::MAX_BIN_PER_POSITION;
 use crate::errors::LBError;
+use crate::instructions::deposit::validate_add_liquidity_access;
 use crate::manager::bin_array_manager::BinArrayManager;
 use crate::math::safe_math::SafeMath;
 use crate::math::weight_to_amounts::{to_amount_ask_side, to_amount_bid_side, to_amount_both_side};
@@ -231,6 +232,15 @@ pub fn handle<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
     liquidity_parameter: &LiquidityParameterByStrategy,
 ) -> Result<()> {
+    let mut remaining_accounts = ctx.remaining_accounts;
+    validate_add_liquidity_access(
+        &ctx.accounts.lb_pair,
+        ctx.accounts.sender.key(),
+        &mut remaining_accounts,
+        liquidity_parameter.amount_x,
+        liquidity_parameter.amount_y,
+    )?;
+
     Ok(())
 }
 
@@ -2,11 +2,14 @@ use super::add_liquidity_by_strategy::StrategyParameters;
 use super::to_weight_ascending_order;
 use super::to_weight_descending_order;
 use super::to_weight_spot_balanced;
+use super::validate_add_liquidity_one_side_access;
 use super::ModifyLiquidityOneSide;
 use super::StrategyType;
 use crate::errors::LBError;
+use crate::events::UpdateRangeOrder;
 use crate::math::weight_to_amounts::to_amount_ask_side;
 use crate::math::weight_to_amounts::to_amount_bid_side;
+use crate::state::position::RangeOrderSide;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Eq, PartialEq, Clone, Debug, Default)]
@@ -19,6 +22,8 @@ pub struct LiquidityParameterByStrategyOneSide {
     pub max_active_bin_slippage: i32,
     /// strategy parameters
     pub strategy_parameters: StrategyParameters,
+    /// Flag the position as range order. Anyone can close it once the active bin has fully crossed the position range.
+    pub range_order: bool,
 }
 
 impl LiquidityParameterByStrategyOneSide {
@@ -66,5 +71,34 @@ pub fn handle<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityOneSide<'info>>,
     liquidity_parameter: &LiquidityParameterByStrategyOneSide,
 ) -> Result<()> {
+    let mut remaining_accounts = ctx.remaining_accounts;
+    validate_add_liquidity_one_side_access(
+        &ctx.accounts.lb_pair,
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.sender.key(),
+        &mut remaining_accounts,
+        liquidity_parameter.amount,
+    )?;
+
+    if liquidity_parameter.range_order {
+        let lb_pair = ctx.accounts.lb_pair.load()?;
+        let mut position = ctx.accounts.position.load_mut()?;
+
+        // Ask side sell token X above the active bin, bid side sell token Y below
+        let range_order_side = position.get_range_order_side(lb_pair.active_id)?;
+        let deposit_for_y = ctx.accounts.token_mint.key().eq(&lb_pair.token_y_mint);
+        require!(
+            deposit_for_y == (range_order_side == RangeOrderSide::Bid),
+            LBError::InvalidRangeOrder
+        );
+
+        position.range_order_side = range_order_side.into();
+
+        emit_cpi!(UpdateRangeOrder {
+            position: ctx.accounts.position.key(),
+            range_order_side: position.range_order_side,
+        });
+    }
+
     Ok(())
 }
@@ -1,6 +1,7 @@
 use crate::authorize_modify_position;
 use crate::constants::MAX_BIN_PER_POSITION;
 use crate::errors::LBError;
+use crate::instructions::deposit::validate_add_liquidity_one_side_access;
 use crate::math::weight_to_amounts::to_amount_ask_side;
 use crate::math::weight_to_amounts::to_amount_bid_side;
 use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
@@ -142,5 +143,14 @@ pub fn handle<'a, 'b, 'c, 'info>(
     ctx: &Context<'a, 'b, 'c, 'info, ModifyLiquidityOneSide<'info>>,
     liquidity_parameter: &LiquidityOneSideParameter,
 ) -> Result<()> {
+    let mut remaining_accounts = ctx.remaining_accounts;
+    validate_add_liquidity_one_side_access(
+        &ctx.accounts.lb_pair,
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.sender.key(),
+        &mut remaining_accounts,
+        liquidity_parameter.amount,
+    )?;
+
     Ok(())
 }
@@ -1,4 +1,6 @@
+use super::validate_add_liquidity_one_side_access;
 use super::ModifyLiquidityOneSide;
+use crate::math::safe_math::SafeMath;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
@@ -17,5 +19,18 @@ pub fn handle<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityOneSide<'info>>,
     parameter: AddLiquiditySingleSidePreciseParameter,
 ) -> Result<()> {
+    let amount = parameter.bins.iter().try_fold(0u64, |total, bin| {
+        total.safe_add(u64::from(bin.amount).safe_mul(parameter.decompress_multiplier)?)
+    })?;
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+    validate_add_liquidity_one_side_access(
+        &ctx.accounts.lb_pair,
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.sender.key(),
+        &mut remaining_accounts,
+        amount,
+    )?;
+
     Ok(())
 }
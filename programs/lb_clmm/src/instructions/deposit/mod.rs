@@ -1,3 +1,9 @@
+use crate::errors::LBError;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::lb_pair::LbPair;
+use crate::state::whitelisted_wallet::load_whitelisted_wallet;
+use anchor_lang::prelude::*;
+
 pub mod add_liquidity;
 pub mod add_liquidity_by_strategy;
 pub mod add_liquidity_by_strategy_one_side;
@@ -11,3 +17,52 @@ pub use add_liquidity_by_strategy_one_side::*;
 pub use add_liquidity_by_weight::*;
 pub use add_liquidity_by_weight_one_side::*;
 pub use add_liquidity_single_side_precise::*;
+
+/// Validate the sender is allowed to add liquidity to the pair. Whitelisted wallet of the sender is passed as the first remaining account. Its deposit caps are consumed by the deposit amounts during the pre-activation window.
+pub fn validate_add_liquidity_access<'c, 'info>(
+    lb_pair: &AccountLoader<'info, LbPair>,
+    sender: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    amount_x: u64,
+    amount_y: u64,
+) -> Result<()> {
+    let whitelisted_wallet = load_whitelisted_wallet(remaining_accounts, lb_pair.key(), sender)?;
+    let mut whitelisted_wallet = whitelisted_wallet
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+
+    let lb_pair = lb_pair.load()?;
+    let pair_type_access_validator = get_lb_pair_type_access_validator(&lb_pair)?;
+    require!(
+        pair_type_access_validator
+            .validate_add_liquidity_access(sender, whitelisted_wallet.as_deref()),
+        LBError::PoolDisabled
+    );
+
+    if pair_type_access_validator.is_in_pre_activation_window() {
+        if let Some(whitelisted_wallet) = whitelisted_wallet.as_mut() {
+            whitelisted_wallet.accumulate_deposit_amount(amount_x, amount_y)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the sender is allowed to add one side liquidity of token_mint to the pair
+pub fn validate_add_liquidity_one_side_access<'c, 'info>(
+    lb_pair: &AccountLoader<'info, LbPair>,
+    token_mint: Pubkey,
+    sender: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    let deposit_for_y = token_mint.eq(&lb_pair.load()?.token_y_mint);
+    let (amount_x, amount_y) = if deposit_for_y {
+        (0, amount)
+    } else {
+        (amount, 0)
+    };
+
+    validate_add_liquidity_access(lb_pair, sender, remaining_accounts, amount_x, amount_y)
+}
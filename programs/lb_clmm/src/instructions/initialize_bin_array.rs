@@ -5,6 +5,7 @@ use anchor_lang::prelude::*;
 #[derive(Accounts)]
 #[instruction(index: i64)]
 pub struct InitializeBinArray<'info> {
+    #[account(mut)]
     pub lb_pair: AccountLoader<'info, LbPair>,
 
     #[account(
@@ -27,5 +28,9 @@ pub struct InitializeBinArray<'info> {
 }
 
 pub fn handle(ctx: Context<InitializeBinArray>, index: i64) -> Result<()> {
+    ctx.accounts
+        .lb_pair
+        .load_mut()?
+        .increase_bin_array_count()?;
     Ok(())
 }
@@ -0,0 +1,299 @@
+use super::CustomizableParams;
+use crate::constants::{
+    DEFAULT_OBSERVATION_LENGTH, ILM_PROTOCOL_SHARE, MAX_BASE_FEE, MAX_BIN_PER_POSITION,
+    MAX_BIN_STEP, MIN_BASE_FEE,
+};
+use crate::errors::LBError;
+use crate::events::PairBootstrap;
+use crate::instructions::deposit::{
+    deposit_position_liquidity, LiquidityParameterByStrategyOneSide, StrategyParameters,
+};
+use crate::manager::bin_array_manager::BinArrayManager;
+use crate::pair_action_access::ActivationType;
+use crate::state::bin::BinArray;
+use crate::state::lb_pair::{LbPair, PairStatus, PairType};
+use crate::state::oracle::{Oracle, OracleContentLoader};
+use crate::state::parameters::StaticParameters;
+use crate::state::position::PositionV2;
+use crate::utils::seeds::{BIN_ARRAY, ILM_BASE_KEY, ORACLE};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TransferChecked};
+use std::cmp::{max, min};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct BootstrapLiquidityParameter {
+    /// Amount of X token to deposit
+    pub amount: u64,
+    /// Strategy parameters. Must be one side strategy within the active bin array.
+    pub strategy_parameters: StrategyParameters,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct BootstrapPairParams {
+    /// Parameters of the customizable permissionless pair
+    pub pair: CustomizableParams,
+    /// Seed liquidity of the pair
+    pub liquidity: BootstrapLiquidityParameter,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: BootstrapPairParams)]
+pub struct BootstrapPair<'info> {
+    #[account(
+        init,
+        seeds = [
+            ILM_BASE_KEY.as_ref(),
+            min(token_mint_x.key(), token_mint_y.key()).as_ref(),
+            max(token_mint_x.key(), token_mint_y.key()).as_ref(),
+        ],
+        bump,
+        payer = funder,
+        space = 8 + LbPair::INIT_SPACE
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(constraint = token_mint_x.key() != token_mint_y.key())]
+    pub token_mint_x: Box<InterfaceAccount<'info, Mint>>,
+    pub token_mint_y: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [
+            lb_pair.key().as_ref(),
+            token_mint_x.key().as_ref()
+        ],
+        bump,
+        payer = funder,
+        token::mint = token_mint_x,
+        token::authority = lb_pair,
+    )]
+    pub reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        seeds = [
+            lb_pair.key().as_ref(),
+            token_mint_y.key().as_ref()
+        ],
+        bump,
+        payer = funder,
+        token::mint = token_mint_y,
+        token::authority = lb_pair,
+    )]
+    pub reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds = [
+            ORACLE,
+            lb_pair.key().as_ref()
+        ],
+        bump,
+        payer = funder,
+        space = Oracle::space(DEFAULT_OBSERVATION_LENGTH)
+    )]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    /// Bin array of the active bin. The seed liquidity must be within this bin array.
+    #[account(
+        init,
+        seeds = [
+            BIN_ARRAY,
+            lb_pair.key().as_ref(),
+            &i64::from(BinArray::bin_id_to_bin_array_index(params.pair.active_id)?).to_le_bytes()
+        ],
+        bump,
+        payer = funder,
+        space = 8 + BinArray::INIT_SPACE
+    )]
+    pub bin_array: AccountLoader<'info, BinArray>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + PositionV2::INIT_SPACE,
+    )]
+    pub position: AccountLoader<'info, PositionV2>,
+
+    #[account(
+        mut,
+        token::authority = funder,
+        token::mint = token_mint_x,
+    )]
+    pub user_token_x: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl BootstrapLiquidityParameter {
+    /// Seed liquidity is ask side only, and must fit in one position, and the active bin array
+    pub fn validate(&self, active_id: i32) -> Result<()> {
+        require!(self.amount > 0, LBError::InvalidInput);
+
+        let min_bin_id = self.strategy_parameters.min_bin_id;
+        let max_bin_id = self.strategy_parameters.max_bin_id;
+
+        let bin_count = self.strategy_parameters.bin_count()?;
+        require!(
+            min_bin_id >= active_id && bin_count > 0 && bin_count <= MAX_BIN_PER_POSITION,
+            LBError::InvalidStrategyParameters
+        );
+
+        let active_bin_array_index = BinArray::bin_id_to_bin_array_index(active_id)?;
+        require!(
+            BinArray::bin_id_to_bin_array_index(max_bin_id)? == active_bin_array_index,
+            LBError::BootstrapRangeOutOfBinArray
+        );
+
+        Ok(())
+    }
+
+    /// Amount of X token deposited into each bin
+    pub fn to_amounts_into_bin(&self, active_id: i32, bin_step: u16) -> Result<Vec<(i32, u64)>> {
+        LiquidityParameterByStrategyOneSide {
+            amount: self.amount,
+            active_id,
+            max_active_bin_slippage: 0,
+            strategy_parameters: self.strategy_parameters.clone(),
+            range_order: false,
+        }
+        .to_amounts_into_bin(active_id, bin_step, false)
+    }
+}
+
+impl<'info> BootstrapPair<'info> {
+    fn transfer_to_lb_pair(&self, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.user_token_x.to_account_info(),
+                    to: self.reserve_x.to_account_info(),
+                    authority: self.funder.to_account_info(),
+                    mint: self.token_mint_x.to_account_info(),
+                },
+            ),
+            amount,
+            self.token_mint_x.decimals,
+        )
+    }
+}
+
+/// Create a customizable permissionless pair, the active bin array, and a position seeded with single side X token liquidity.
+/// The active bin array must be within the default bin array bitmap of the pair as no bitmap extension is created.
+pub fn handle(ctx: Context<BootstrapPair>, params: BootstrapPairParams) -> Result<()> {
+    let pair_params = &params.pair;
+    let active_id = pair_params.active_id;
+    let bin_step = pair_params.bin_step;
+
+    require!(
+        bin_step > 0 && bin_step <= MAX_BIN_STEP,
+        LBError::InvalidBinStep
+    );
+    params.liquidity.validate(active_id)?;
+
+    let activation_type = ActivationType::try_from(pair_params.activation_type)
+        .map_err(|_| LBError::InvalidActivationType)?;
+    let current_point = match activation_type {
+        ActivationType::Slot => Clock::get()?.slot,
+        ActivationType::Timestamp => Clock::get()?.unix_timestamp as u64,
+    };
+    let activation_point = pair_params.activation_point.unwrap_or(current_point);
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let mut lb_pair = ctx.accounts.lb_pair.load_init()?;
+    lb_pair.initialize(
+        *ctx.bumps.get("lb_pair").unwrap(),
+        active_id,
+        bin_step,
+        ctx.accounts.token_mint_x.key(),
+        ctx.accounts.token_mint_y.key(),
+        ctx.accounts.reserve_x.key(),
+        ctx.accounts.reserve_y.key(),
+        ctx.accounts.oracle.key(),
+        StaticParameters {
+            base_factor: pair_params.base_factor,
+            protocol_share: ILM_PROTOCOL_SHARE,
+            ..Default::default()
+        },
+        PairType::CustomizablePermissionless,
+        PairStatus::Enabled.into(),
+        ILM_BASE_KEY,
+        ctx.accounts.funder.key(),
+        pair_params.activation_type,
+        activation_point,
+        Pubkey::default(),
+        0,
+    )?;
+    lb_pair.creator_pool_on_off_control = pair_params.creator_pool_on_off_control.into();
+
+    let base_fee = lb_pair.get_base_fee()?;
+    require!(
+        base_fee >= MIN_BASE_FEE && base_fee <= MAX_BASE_FEE,
+        LBError::InvalidBaseFee
+    );
+
+    ctx.accounts.oracle.load_content_init()?.metadata.init();
+
+    let mut bin_array = ctx.accounts.bin_array.load_init()?;
+    bin_array.initialize(
+        BinArray::bin_id_to_bin_array_index(active_id)?.into(),
+        lb_pair_key,
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut position = ctx.accounts.position.load_init()?;
+    position.init(
+        lb_pair_key,
+        ctx.accounts.funder.key(),
+        Pubkey::default(),
+        params.liquidity.strategy_parameters.min_bin_id,
+        params.liquidity.strategy_parameters.max_bin_id,
+        current_time,
+        0,
+        Pubkey::default(),
+        ctx.accounts.funder.key(),
+    )?;
+
+    lb_pair.increase_position_count()?;
+    lb_pair.increase_bin_array_count()?;
+
+    let amounts_into_bin = params
+        .liquidity
+        .to_amounts_into_bin(active_id, bin_step)?
+        .into_iter()
+        .map(|(bin_id, amount)| (bin_id, amount, 0))
+        .collect::<Vec<(i32, u64, u64)>>();
+
+    let mut bin_arrays = [bin_array];
+    let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays)?;
+    let (amount_x, _amount_y) = deposit_position_liquidity(
+        &mut lb_pair,
+        &mut position,
+        &mut bin_array_manager,
+        &None,
+        &amounts_into_bin,
+    )?;
+    position.set_last_updated_at(current_time);
+
+    drop(bin_arrays);
+    drop(position);
+    drop(lb_pair);
+
+    ctx.accounts.transfer_to_lb_pair(amount_x)?;
+
+    emit_cpi!(PairBootstrap {
+        lb_pair: lb_pair_key,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.funder.key(),
+        active_id,
+        amount_x,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
+pub mod bootstrap_pair;
 pub mod initialize_customizable_permissionless_lb_pair;
 pub mod initialize_permission_lb_pair;
 pub mod initialize_permissionless_lb_pair;
 
+pub use bootstrap_pair::*;
 pub use initialize_customizable_permissionless_lb_pair::*;
 pub use initialize_permission_lb_pair::*;
 pub use initialize_permissionless_lb_pair::*;
@@ -0,0 +1,83 @@
+use crate::errors::LBError;
+use crate::state::lb_pair::LbPair;
+use crate::state::whitelisted_wallet::WhitelistedWallet;
+use crate::utils::merkle::{get_whitelist_leaf, verify_proof};
+use crate::utils::seeds::WHITELISTED_WALLET;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Eq, PartialEq, Clone, Debug)]
+pub struct WhitelistedWalletParameter {
+    /// Maximum amount of token X the wallet can swap in during the pre-activation window
+    pub max_swap_amount_x: u64,
+    /// Maximum amount of token Y the wallet can swap in during the pre-activation window
+    pub max_swap_amount_y: u64,
+    /// Maximum amount of token X the wallet can deposit during the pre-activation window
+    pub max_deposit_amount_x: u64,
+    /// Maximum amount of token Y the wallet can deposit during the pre-activation window
+    pub max_deposit_amount_y: u64,
+    /// Merkle proof of the wallet leaf
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeWhitelistedWallet<'info> {
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        init,
+        seeds = [
+            WHITELISTED_WALLET,
+            lb_pair.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump,
+        payer = wallet,
+        space = 8 + WhitelistedWallet::INIT_SPACE
+    )]
+    pub whitelisted_wallet: AccountLoader<'info, WhitelistedWallet>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl WhitelistedWalletParameter {
+    /// Verify the wallet and its caps are in the whitelist merkle tree of the pair
+    pub fn verify(&self, lb_pair: &LbPair, wallet: Pubkey) -> Result<()> {
+        require!(lb_pair.is_permission_pair()?, LBError::InvalidPoolType);
+        require!(lb_pair.is_whitelist_enabled(), LBError::WhitelistNotEnabled);
+
+        let leaf = get_whitelist_leaf(
+            wallet,
+            self.max_swap_amount_x,
+            self.max_swap_amount_y,
+            self.max_deposit_amount_x,
+            self.max_deposit_amount_y,
+        );
+        require!(
+            verify_proof(&self.proof, lb_pair.whitelist_merkle_root, leaf),
+            LBError::InvalidMerkleProof
+        );
+
+        Ok(())
+    }
+}
+
+pub fn handle(
+    ctx: Context<InitializeWhitelistedWallet>,
+    parameter: WhitelistedWalletParameter,
+) -> Result<()> {
+    let lb_pair = ctx.accounts.lb_pair.load()?;
+    parameter.verify(&lb_pair, ctx.accounts.wallet.key())?;
+
+    let mut whitelisted_wallet = ctx.accounts.whitelisted_wallet.load_init()?;
+    whitelisted_wallet.init(
+        ctx.accounts.lb_pair.key(),
+        ctx.accounts.wallet.key(),
+        lb_pair.whitelist_merkle_root,
+        &parameter,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,421 @@
+use crate::authorize_modify_position;
+use crate::constants::MAX_BIN_PER_POSITION;
+use crate::errors::LBError;
+use crate::events::MigrateLiquidity as MigrateLiquidityEvent;
+use crate::instructions::deposit::{
+    deposit_position_liquidity, validate_add_liquidity_access,
+    validate_add_liquidity_by_strategy_params, LiquidityParameterByStrategy, StrategyParameters,
+    StrategyType,
+};
+use crate::instructions::position_authorize::PositionLiquidityFlowValidator;
+use crate::instructions::withdraw::remove_liquidity::withdraw_all_position_liquidity;
+use crate::manager::bin_array_manager::BinArrayManager;
+use crate::math::price_math::{get_id_from_price, get_price_from_id};
+use crate::math::safe_math::SafeMath;
+use crate::math::u128x128_math::Rounding;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
+use crate::state::position::PositionV2;
+use crate::state::{bin::BinArray, lb_pair::LbPair};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Eq, PartialEq, Clone, Debug)]
+pub struct MigrateLiquidityParameter {
+    /// Minimum amount of token X withdrawn from the source position to be deposited into the destination position
+    pub min_amount_x: u64,
+    /// Minimum amount of token Y withdrawn from the source position to be deposited into the destination position
+    pub min_amount_y: u64,
+    /// Active bin of the destination pair that integrator observe off-chain
+    pub active_id: i32,
+    /// max active bin slippage allowed
+    pub max_active_bin_slippage: i32,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateLiquidity<'info> {
+    #[account(
+        mut,
+        has_one = lb_pair,
+        constraint = authorize_modify_position(&position, sender.key())?
+    )]
+    pub position: AccountLoader<'info, PositionV2>,
+
+    #[account(
+        mut,
+        has_one = reserve_x,
+        has_one = reserve_y,
+        has_one = token_x_mint,
+        has_one = token_y_mint,
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        mut,
+        has_one = lb_pair,
+    )]
+    pub bin_array_bitmap_extension: Option<AccountLoader<'info, BinArrayBitmapExtension>>,
+
+    #[account(
+        mut,
+        has_one = lb_pair
+    )]
+    pub bin_array_lower: AccountLoader<'info, BinArray>,
+    #[account(
+        mut,
+        has_one = lb_pair
+    )]
+    pub bin_array_upper: AccountLoader<'info, BinArray>,
+
+    #[account(mut)]
+    pub reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = dest_position.load()?.lb_pair == dest_lb_pair.key() @ LBError::InvalidPosition,
+        constraint = dest_position.load()?.owner == position.load()?.owner @ LBError::InvalidPosition,
+        constraint = authorize_modify_position(&dest_position, sender.key())?
+    )]
+    pub dest_position: AccountLoader<'info, PositionV2>,
+
+    #[account(
+        mut,
+        constraint = dest_lb_pair.key() != lb_pair.key() @ LBError::InvalidInput,
+        constraint = dest_lb_pair.load()?.token_x_mint == token_x_mint.key() @ LBError::InvalidTokenMint,
+        constraint = dest_lb_pair.load()?.token_y_mint == token_y_mint.key() @ LBError::InvalidTokenMint,
+        constraint = dest_lb_pair.load()?.bin_step != lb_pair.load()?.bin_step @ LBError::InvalidBinStep,
+    )]
+    pub dest_lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        mut,
+        constraint = dest_bin_array_bitmap_extension.load()?.lb_pair == dest_lb_pair.key() @ LBError::InvalidInput,
+    )]
+    pub dest_bin_array_bitmap_extension: Option<AccountLoader<'info, BinArrayBitmapExtension>>,
+
+    #[account(
+        mut,
+        constraint = dest_bin_array_lower.load()?.lb_pair == dest_lb_pair.key() @ LBError::InvalidBinArray
+    )]
+    pub dest_bin_array_lower: AccountLoader<'info, BinArray>,
+    #[account(
+        mut,
+        constraint = dest_bin_array_upper.load()?.lb_pair == dest_lb_pair.key() @ LBError::InvalidBinArray
+    )]
+    pub dest_bin_array_upper: AccountLoader<'info, BinArray>,
+
+    #[account(
+        mut,
+        address = dest_lb_pair.load()?.reserve_x
+    )]
+    pub dest_reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = dest_lb_pair.load()?.reserve_y
+    )]
+    pub dest_reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_x_mint
+    )]
+    pub user_token_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_y_mint
+    )]
+    pub user_token_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_x_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_y_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub sender: Signer<'info>,
+    pub token_x_program: Interface<'info, TokenInterface>,
+    pub token_y_program: Interface<'info, TokenInterface>,
+}
+
+/// Map the bin range of a pair to the bin range of another pair with different bin step, which cover the same price range.
+pub fn get_migrated_bin_range(
+    lower_bin_id: i32,
+    upper_bin_id: i32,
+    bin_step: u16,
+    dest_bin_step: u16,
+) -> Result<(i32, i32)> {
+    let lower_price = get_price_from_id(lower_bin_id, bin_step)?;
+    let upper_price = get_price_from_id(upper_bin_id, bin_step)?;
+
+    let min_bin_id = get_id_from_price(lower_price, dest_bin_step, Rounding::Down)?;
+    let max_bin_id = get_id_from_price(upper_price, dest_bin_step, Rounding::Up)?;
+
+    let width = max_bin_id.safe_sub(min_bin_id)?.safe_add(1)?;
+    require!(
+        width <= MAX_BIN_PER_POSITION as i32,
+        LBError::InvalidPositionWidth
+    );
+
+    Ok((min_bin_id, max_bin_id))
+}
+
+impl<'info> MigrateLiquidity<'info> {
+    fn transfer_from_lb_pair(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program,
+                TransferChecked {
+                    from,
+                    to,
+                    authority: self.lb_pair.to_account_info(),
+                    mint: mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+}
+
+impl<'info> PositionLiquidityFlowValidator for MigrateLiquidity<'info> {
+    fn validate_outflow_to_ata_of_position_owner(&self, owner: Pubkey) -> Result<()> {
+        let owner_ata_x = get_associated_token_address_with_program_id(
+            &owner,
+            &self.token_x_mint.key(),
+            &self.token_x_program.key(),
+        );
+        let owner_ata_y = get_associated_token_address_with_program_id(
+            &owner,
+            &self.token_y_mint.key(),
+            &self.token_y_program.key(),
+        );
+
+        require!(
+            owner_ata_x == self.user_token_x.key() && owner_ata_y == self.user_token_y.key(),
+            LBError::WithdrawToWrongTokenAccount
+        );
+
+        Ok(())
+    }
+}
+
+/// Withdraw all liquidity of the position, and deposit it spot balanced into the destination position over the same price range of the destination pair.
+/// Fees, and rewards remain claimable in the source position. Amount not deposited due to rounding is returned to the position owner.
+pub fn handle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MigrateLiquidity<'info>>,
+    parameter: MigrateLiquidityParameter,
+) -> Result<()> {
+    let owner = ctx.accounts.position.load()?.owner;
+    if owner != ctx.accounts.sender.key() {
+        ctx.accounts
+            .validate_outflow_to_ata_of_position_owner(owner)?;
+    }
+
+    let (lower_bin_id, upper_bin_id, amount_x, amount_y) = {
+        let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+        let mut position = ctx.accounts.position.load_mut()?;
+
+        let current_point = get_lb_pair_type_access_validator(&lb_pair)?.get_current_point();
+        require!(
+            !position.is_liquidity_locked(current_point),
+            LBError::LiquidityLocked
+        );
+
+        let mut bin_arrays = [
+            ctx.accounts.bin_array_lower.load_mut()?,
+            ctx.accounts.bin_array_upper.load_mut()?,
+        ];
+        let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays)?;
+
+        let (amount_x, amount_y) = withdraw_all_position_liquidity(
+            &mut lb_pair,
+            &mut position,
+            &mut bin_array_manager,
+            &ctx.accounts.bin_array_bitmap_extension,
+        )?;
+        position.set_last_updated_at(Clock::get()?.unix_timestamp);
+
+        (
+            position.lower_bin_id,
+            position.upper_bin_id,
+            amount_x,
+            amount_y,
+        )
+    };
+
+    require!(
+        amount_x >= parameter.min_amount_x && amount_y >= parameter.min_amount_y,
+        LBError::ExceededAmountSlippageTolerance
+    );
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+    validate_add_liquidity_access(
+        &ctx.accounts.dest_lb_pair,
+        ctx.accounts.sender.key(),
+        &mut remaining_accounts,
+        amount_x,
+        amount_y,
+    )?;
+
+    let (deposit_amount_x, deposit_amount_y) = {
+        let bin_step = ctx.accounts.lb_pair.load()?.bin_step;
+        let mut dest_lb_pair = ctx.accounts.dest_lb_pair.load_mut()?;
+        let mut dest_position = ctx.accounts.dest_position.load_mut()?;
+
+        let (min_bin_id, max_bin_id) =
+            get_migrated_bin_range(lower_bin_id, upper_bin_id, bin_step, dest_lb_pair.bin_step)?;
+
+        require!(
+            dest_position.lower_bin_id <= min_bin_id && max_bin_id <= dest_position.upper_bin_id,
+            LBError::InvalidPosition
+        );
+
+        let liquidity_parameter = LiquidityParameterByStrategy {
+            amount_x,
+            amount_y,
+            active_id: parameter.active_id,
+            max_active_bin_slippage: parameter.max_active_bin_slippage,
+            strategy_parameters: StrategyParameters {
+                min_bin_id,
+                max_bin_id,
+                strategy_type: StrategyType::SpotBalanced,
+                parameteres: [0u8; 64],
+            },
+        };
+
+        validate_add_liquidity_by_strategy_params(
+            liquidity_parameter.active_id,
+            dest_lb_pair.active_id,
+            liquidity_parameter.max_active_bin_slippage,
+            &liquidity_parameter.strategy_parameters,
+        )?;
+
+        let mut bin_arrays = [
+            ctx.accounts.dest_bin_array_lower.load_mut()?,
+            ctx.accounts.dest_bin_array_upper.load_mut()?,
+        ];
+        let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays)?;
+
+        let (amount_x_in_active_bin, amount_y_in_active_bin) = bin_array_manager
+            .get_bin(dest_lb_pair.active_id)
+            .map(|bin| (bin.amount_x, bin.amount_y))
+            .unwrap_or((0, 0));
+
+        let amounts_into_bin = liquidity_parameter.to_amounts_into_bin(
+            dest_lb_pair.active_id,
+            dest_lb_pair.bin_step,
+            amount_x_in_active_bin,
+            amount_y_in_active_bin,
+        )?;
+
+        let (deposit_amount_x, deposit_amount_y) = deposit_position_liquidity(
+            &mut dest_lb_pair,
+            &mut dest_position,
+            &mut bin_array_manager,
+            &ctx.accounts.dest_bin_array_bitmap_extension,
+            &amounts_into_bin,
+        )?;
+        dest_position.set_last_updated_at(Clock::get()?.unix_timestamp);
+
+        (deposit_amount_x, deposit_amount_y)
+    };
+
+    let lb_pair_seeds = ctx.accounts.lb_pair.load()?.signer_seeds()?;
+    let lb_pair_seeds = lb_pair_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect::<Vec<&[u8]>>();
+    let signer_seeds = &[&lb_pair_seeds[..]];
+
+    let accounts = &ctx.accounts;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_x.to_account_info(),
+        accounts.dest_reserve_x.to_account_info(),
+        &accounts.token_x_mint,
+        accounts.token_x_program.to_account_info(),
+        signer_seeds,
+        deposit_amount_x,
+    )?;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_y.to_account_info(),
+        accounts.dest_reserve_y.to_account_info(),
+        &accounts.token_y_mint,
+        accounts.token_y_program.to_account_info(),
+        signer_seeds,
+        deposit_amount_y,
+    )?;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_x.to_account_info(),
+        accounts.user_token_x.to_account_info(),
+        &accounts.token_x_mint,
+        accounts.token_x_program.to_account_info(),
+        signer_seeds,
+        amount_x.safe_sub(deposit_amount_x)?,
+    )?;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_y.to_account_info(),
+        accounts.user_token_y.to_account_info(),
+        &accounts.token_y_mint,
+        accounts.token_y_program.to_account_info(),
+        signer_seeds,
+        amount_y.safe_sub(deposit_amount_y)?,
+    )?;
+
+    emit_cpi!(MigrateLiquidityEvent {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        position: ctx.accounts.position.key(),
+        dest_lb_pair: ctx.accounts.dest_lb_pair.key(),
+        dest_position: ctx.accounts.dest_position.key(),
+        amounts: [amount_x, amount_y],
+        dest_amounts: [deposit_amount_x, deposit_amount_y],
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_migrated_bin_range_cover_source_price_range() {
+        for (bin_step, dest_bin_step) in [(10, 25), (25, 10), (1, 100), (100, 80)] {
+            for (lower_bin_id, upper_bin_id) in [(-10, 10), (0, 0), (500, 520)] {
+                let (min_bin_id, max_bin_id) =
+                    get_migrated_bin_range(lower_bin_id, upper_bin_id, bin_step, dest_bin_step)
+                        .unwrap();
+
+                let lower_price = get_price_from_id(lower_bin_id, bin_step).unwrap();
+                let upper_price = get_price_from_id(upper_bin_id, bin_step).unwrap();
+
+                assert!(get_price_from_id(min_bin_id, dest_bin_step).unwrap() <= lower_price);
+                assert!(get_price_from_id(min_bin_id + 1, dest_bin_step).unwrap() > lower_price);
+                assert!(get_price_from_id(max_bin_id, dest_bin_step).unwrap() >= upper_price);
+                assert!(get_price_from_id(max_bin_id - 1, dest_bin_step).unwrap() < upper_price);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_migrated_bin_range_exceed_position_width() {
+        // 70 bins of bin step 100 span ~140 bins of bin step 50
+        assert!(get_migrated_bin_range(0, 69, 100, 50).is_err());
+        assert!(get_migrated_bin_range(0, 34, 100, 50).is_ok());
+    }
+}
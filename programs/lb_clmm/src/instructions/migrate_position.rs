@@ -25,6 +25,7 @@ pub struct MigratePosition<'info> {
     )]
     pub position_v1: AccountLoader<'info, Position>,
 
+    #[account(mut)]
     pub lb_pair: AccountLoader<'info, LbPair>,
 
     #[account(
@@ -49,5 +50,8 @@ pub struct MigratePosition<'info> {
 }
 
 pub fn handle(ctx: Context<MigratePosition>) -> Result<()> {
+    // Position v1 was never counted
+    ctx.accounts.lb_pair.load_mut()?.increase_position_count()?;
+
     Ok(())
 }
@@ -1,6 +1,9 @@
 pub mod admin;
+pub mod claim_all;
 pub mod claim_fee;
 pub mod claim_reward;
+pub mod close_bin_array;
+pub mod close_lb_pair;
 pub mod close_position;
 pub mod create_position;
 pub mod deposit;
@@ -9,11 +12,18 @@ pub mod increase_oracle_length;
 pub mod initialize_bin_array;
 pub mod initialize_bin_array_bitmap_extension;
 pub mod initialize_pool;
+pub mod initialize_whitelisted_wallet;
 pub mod migrate_bin_array;
+pub mod migrate_liquidity;
 pub mod migrate_position;
 pub mod position_authorize;
+pub mod position_trigger;
+pub mod quote_swap;
+pub mod range_order;
+pub mod refresh_whitelisted_wallet;
 pub mod set_pair_status_permissionless;
 pub mod swap;
+pub mod sync_pair_to_preset;
 pub mod update_fees_and_rewards;
 pub mod update_position_operator;
 pub mod withdraw;
@@ -0,0 +1,269 @@
+use crate::errors::LBError;
+use crate::events::ExecutePositionTrigger as ExecutePositionTriggerEvent;
+use crate::instructions::position_authorize::PositionLiquidityFlowValidator;
+use crate::instructions::withdraw::remove_liquidity::withdraw_all_position_liquidity;
+use crate::manager::bin_array_manager::BinArrayManager;
+use crate::math::safe_math::SafeMath;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
+use crate::state::oracle::{Oracle, OracleContentLoader};
+use crate::state::position::PositionV2;
+use crate::state::{bin::BinArray, lb_pair::LbPair};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecutePositionTrigger<'info> {
+    #[account(
+        mut,
+        has_one = lb_pair,
+        has_one = owner,
+    )]
+    pub position: AccountLoader<'info, PositionV2>,
+
+    #[account(
+        mut,
+        has_one = reserve_x,
+        has_one = reserve_y,
+        has_one = token_x_mint,
+        has_one = token_y_mint,
+        has_one = oracle,
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        mut,
+        has_one = lb_pair,
+    )]
+    pub bin_array_bitmap_extension: Option<AccountLoader<'info, BinArrayBitmapExtension>>,
+
+    #[account(
+        mut,
+        has_one = lb_pair
+    )]
+    pub bin_array_lower: AccountLoader<'info, BinArray>,
+    #[account(
+        mut,
+        has_one = lb_pair
+    )]
+    pub bin_array_upper: AccountLoader<'info, BinArray>,
+
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    #[account(mut)]
+    pub reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Position owner. Receive the withdrawn liquidity.
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_x_mint
+    )]
+    pub owner_token_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_y_mint
+    )]
+    pub owner_token_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_x_mint,
+        token::authority = keeper,
+    )]
+    pub keeper_token_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_y_mint,
+        token::authority = keeper,
+    )]
+    pub keeper_token_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_x_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_y_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub keeper: Signer<'info>,
+    pub token_x_program: Interface<'info, TokenInterface>,
+    pub token_y_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExecutePositionTrigger<'info> {
+    fn transfer_from_lb_pair(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program,
+                TransferChecked {
+                    from,
+                    to,
+                    authority: self.lb_pair.to_account_info(),
+                    mint: mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+}
+
+impl<'info> PositionLiquidityFlowValidator for ExecutePositionTrigger<'info> {
+    fn validate_outflow_to_ata_of_position_owner(&self, owner: Pubkey) -> Result<()> {
+        let owner_ata_x = get_associated_token_address_with_program_id(
+            &owner,
+            &self.token_x_mint.key(),
+            &self.token_x_program.key(),
+        );
+        let owner_ata_y = get_associated_token_address_with_program_id(
+            &owner,
+            &self.token_y_mint.key(),
+            &self.token_y_program.key(),
+        );
+
+        require!(
+            owner_ata_x == self.owner_token_x.key() && owner_ata_y == self.owner_token_y.key(),
+            LBError::WithdrawToWrongTokenAccount
+        );
+
+        Ok(())
+    }
+}
+
+/// Permissionless. Withdraw all liquidity of the position to the owner when the trigger fired, and pay the keeper bounty from the withdrawn amount.
+pub fn handle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ExecutePositionTrigger<'info>>,
+) -> Result<()> {
+    ctx.accounts
+        .validate_outflow_to_ata_of_position_owner(ctx.accounts.owner.key())?;
+
+    let (price_bin_id, amount_x, amount_y) = {
+        let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+        let mut position = ctx.accounts.position.load_mut()?;
+
+        require!(
+            position.trigger.is_enabled(),
+            LBError::InvalidPositionTrigger
+        );
+
+        let current_point = get_lb_pair_type_access_validator(&lb_pair)?.get_current_point();
+        require!(
+            !position.is_liquidity_locked(current_point),
+            LBError::LiquidityLocked
+        );
+
+        let price_bin_id = if position.trigger.twap_window > 0 {
+            let oracle = ctx.accounts.oracle.load_content()?;
+            oracle.get_twap_active_id(
+                lb_pair.active_id,
+                Clock::get()?.unix_timestamp,
+                position.trigger.twap_window.into(),
+            )?
+        } else {
+            lb_pair.active_id
+        };
+
+        require!(
+            position.trigger.is_fired(price_bin_id),
+            LBError::PositionTriggerNotFired
+        );
+
+        let mut bin_arrays = [
+            ctx.accounts.bin_array_lower.load_mut()?,
+            ctx.accounts.bin_array_upper.load_mut()?,
+        ];
+        let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays)?;
+
+        let (amount_x, amount_y) = withdraw_all_position_liquidity(
+            &mut lb_pair,
+            &mut position,
+            &mut bin_array_manager,
+            &ctx.accounts.bin_array_bitmap_extension,
+        )?;
+
+        // One shot. Owner re-attach the trigger if needed.
+        position.trigger.enabled = 0;
+        position.set_last_updated_at(Clock::get()?.unix_timestamp);
+
+        (price_bin_id, amount_x, amount_y)
+    };
+
+    let (keeper_bounty_x, keeper_bounty_y) = {
+        let position = ctx.accounts.position.load()?;
+        (
+            position.trigger.compute_keeper_bounty(amount_x)?,
+            position.trigger.compute_keeper_bounty(amount_y)?,
+        )
+    };
+    let owner_amount_x = amount_x.safe_sub(keeper_bounty_x)?;
+    let owner_amount_y = amount_y.safe_sub(keeper_bounty_y)?;
+
+    let lb_pair_seeds = ctx.accounts.lb_pair.load()?.signer_seeds()?;
+    let lb_pair_seeds = lb_pair_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect::<Vec<&[u8]>>();
+    let signer_seeds = &[&lb_pair_seeds[..]];
+
+    let accounts = &ctx.accounts;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_x.to_account_info(),
+        accounts.owner_token_x.to_account_info(),
+        &accounts.token_x_mint,
+        accounts.token_x_program.to_account_info(),
+        signer_seeds,
+        owner_amount_x,
+    )?;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_y.to_account_info(),
+        accounts.owner_token_y.to_account_info(),
+        &accounts.token_y_mint,
+        accounts.token_y_program.to_account_info(),
+        signer_seeds,
+        owner_amount_y,
+    )?;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_x.to_account_info(),
+        accounts.keeper_token_x.to_account_info(),
+        &accounts.token_x_mint,
+        accounts.token_x_program.to_account_info(),
+        signer_seeds,
+        keeper_bounty_x,
+    )?;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_y.to_account_info(),
+        accounts.keeper_token_y.to_account_info(),
+        &accounts.token_y_mint,
+        accounts.token_y_program.to_account_info(),
+        signer_seeds,
+        keeper_bounty_y,
+    )?;
+
+    emit_cpi!(ExecutePositionTriggerEvent {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        position: ctx.accounts.position.key(),
+        keeper: ctx.accounts.keeper.key(),
+        price_bin_id,
+        amounts: [owner_amount_x, owner_amount_y],
+        keeper_bounties: [keeper_bounty_x, keeper_bounty_y],
+    });
+
+    Ok(())
+}
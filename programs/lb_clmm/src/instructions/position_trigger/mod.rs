@@ -0,0 +1,5 @@
+pub mod execute_position_trigger;
+pub mod set_position_trigger;
+
+pub use execute_position_trigger::*;
+pub use set_position_trigger::*;
@@ -0,0 +1,78 @@
+use crate::authorize_modify_position;
+use crate::constants::MAX_KEEPER_BOUNTY_BPS;
+use crate::errors::LBError;
+use crate::events::UpdatePositionTrigger;
+use crate::state::position::{PositionTrigger, PositionV2};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Eq, PartialEq, Clone, Debug)]
+pub struct PositionTriggerParameter {
+    /// Fire when the price bin <= lower_bin_id. Use i32::MIN to disable.
+    pub lower_bin_id: i32,
+    /// Fire when the price bin >= upper_bin_id. Use i32::MAX to disable.
+    pub upper_bin_id: i32,
+    /// TWAP window in seconds. 0 to use the active bin as price bin.
+    pub twap_window: u32,
+    /// Portion of the withdrawn amount paid to the keeper
+    pub keeper_bounty_bps: u16,
+}
+
+impl PositionTriggerParameter {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.lower_bin_id < self.upper_bin_id,
+            LBError::InvalidPositionTrigger
+        );
+        require!(
+            self.keeper_bounty_bps <= MAX_KEEPER_BOUNTY_BPS,
+            LBError::InvalidPositionTrigger
+        );
+        Ok(())
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPositionTrigger<'info> {
+    #[account(
+        mut,
+        constraint = authorize_modify_position(&position, sender.key())?
+    )]
+    pub position: AccountLoader<'info, PositionV2>,
+
+    pub sender: Signer<'info>,
+}
+
+/// Attach the trigger to the position. Passing None remove the trigger.
+pub fn handle(
+    ctx: Context<SetPositionTrigger>,
+    trigger: Option<PositionTriggerParameter>,
+) -> Result<()> {
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    position.trigger = match trigger {
+        Some(parameter) => {
+            parameter.validate()?;
+            PositionTrigger {
+                lower_bin_id: parameter.lower_bin_id,
+                upper_bin_id: parameter.upper_bin_id,
+                twap_window: parameter.twap_window,
+                keeper_bounty_bps: parameter.keeper_bounty_bps,
+                enabled: 1,
+                _padding: 0,
+            }
+        }
+        None => PositionTrigger::default(),
+    };
+
+    emit_cpi!(UpdatePositionTrigger {
+        position: ctx.accounts.position.key(),
+        lower_bin_id: position.trigger.lower_bin_id,
+        upper_bin_id: position.trigger.upper_bin_id,
+        twap_window: position.trigger.twap_window,
+        keeper_bounty_bps: position.trigger.keeper_bounty_bps,
+        enabled: position.trigger.is_enabled(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use crate::constants::NO_FEE_DISCOUNT_MULTIPLIER_BPS;
+use crate::errors::LBError;
+use crate::instructions::swap::swap_bins;
+use crate::manager::bin_array_manager::BinArrayManagerSimulation;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::bin::BinArray;
+use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
+use crate::state::fee_tier::load_fee_tier;
+use crate::state::lb_pair::LbPair;
+use crate::state::whitelisted_wallet::load_whitelisted_wallet;
+use anchor_lang::prelude::*;
+
+/// Result of the swap simulation. Returned to the caller through return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Amount of token swapped into the pair, include fees
+    pub amount_in: u64,
+    /// Amount of token swapped out from the pair
+    pub amount_out: u64,
+    /// Swap fee, includes protocol fee
+    pub fee: u64,
+    /// Part of fee
+    pub protocol_fee: u64,
+    /// Finalized active bin ID
+    pub end_bin_id: i32,
+}
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        has_one = lb_pair,
+    )]
+    pub bin_array_bitmap_extension: Option<AccountLoader<'info, BinArrayBitmapExtension>>,
+
+    /// CHECK: Wallet the quote is for. Swap access, whitelisted wallet, and fee tier are resolved against it.
+    pub user: UncheckedAccount<'info>,
+}
+
+/// Simulate swap exact in against a copy of the pair state. The bins are copied on access, therefore the bin arrays are not mutated.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_swap_exact_in(
+    lb_pair: &mut LbPair,
+    bin_array_manager: &mut BinArrayManagerSimulation,
+    bin_array_bitmap_extension: &Option<AccountLoader<BinArrayBitmapExtension>>,
+    amount_in: u64,
+    swap_for_y: bool,
+    fee_multiplier_bps: u16,
+    current_point: u64,
+    current_timestamp: i64,
+    current_slot: u64,
+) -> Result<SwapQuote> {
+    lb_pair.update_protocol_share(current_point);
+    lb_pair.update_references(current_timestamp)?;
+    let fee_modifier = lb_pair.get_swap_fee_modifier(current_slot, swap_for_y, fee_multiplier_bps);
+
+    let total = swap_bins(
+        lb_pair,
+        bin_array_manager,
+        bin_array_bitmap_extension,
+        swap_for_y,
+        amount_in,
+        None,
+        None,
+        None,
+        &fee_modifier,
+    )?;
+
+    Ok(SwapQuote {
+        amount_in: total.amount_in,
+        amount_out: total.amount_out,
+        fee: total.fee,
+        protocol_fee: total.protocol_fee,
+        end_bin_id: total.end_bin_id,
+    })
+}
+
+/// Whitelisted wallet, and fee tier of the user, if any, are passed as the first remaining accounts in that order. Followed by the bin arrays required by the swap, ordered in the swap direction.
+/// The swap access, and the whitelisted wallet cap are validated the same way as the swap. The quote is written to the return data.
+pub fn handle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, QuoteSwap<'info>>,
+    amount_in: u64,
+    swap_for_y: bool,
+) -> Result<SwapQuote> {
+    require!(amount_in > 0, LBError::InvalidInput);
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let user = ctx.accounts.user.key();
+    let mut remaining_accounts = ctx.remaining_accounts;
+
+    let whitelisted_wallet = load_whitelisted_wallet(&mut remaining_accounts, lb_pair_key, user)?
+        .map(|loader| loader.load().map(|whitelisted_wallet| *whitelisted_wallet))
+        .transpose()?;
+
+    let fee_multiplier_bps = match load_fee_tier(&mut remaining_accounts, lb_pair_key, user)? {
+        Some(fee_tier) => fee_tier.load()?.get_fee_multiplier_bps(),
+        None => NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+    };
+
+    let mut lb_pair = *ctx.accounts.lb_pair.load()?;
+
+    let (current_point, is_in_pre_activation_window) = {
+        let pair_type_access_validator = get_lb_pair_type_access_validator(&lb_pair)?;
+        require!(
+            pair_type_access_validator.validate_swap_access(user, whitelisted_wallet.as_ref()),
+            LBError::PoolDisabled
+        );
+        (
+            pair_type_access_validator.get_current_point(),
+            pair_type_access_validator.is_in_pre_activation_window(),
+        )
+    };
+
+    let bin_array_loaders = remaining_accounts
+        .iter()
+        .map(AccountLoader::<BinArray>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let bin_arrays = bin_array_loaders
+        .iter()
+        .map(|loader| loader.load())
+        .collect::<Result<Vec<_>>>()?;
+
+    for bin_array in bin_arrays.iter() {
+        require!(bin_array.lb_pair == lb_pair_key, LBError::InvalidBinArray);
+    }
+
+    let mut bin_array_manager = BinArrayManagerSimulation::new(&bin_arrays)?;
+    let clock = Clock::get()?;
+
+    let quote = simulate_swap_exact_in(
+        &mut lb_pair,
+        &mut bin_array_manager,
+        &ctx.accounts.bin_array_bitmap_extension,
+        amount_in,
+        swap_for_y,
+        fee_multiplier_bps,
+        current_point,
+        clock.unix_timestamp,
+        clock.slot,
+    )?;
+
+    // Whitelisted wallet is capped during the pre-activation window only
+    if is_in_pre_activation_window {
+        if let Some(mut whitelisted_wallet) = whitelisted_wallet {
+            whitelisted_wallet.accumulate_swap_amount(swap_for_y, quote.amount_in)?;
+        }
+    }
+
+    Ok(quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::swap::tests::{bin_array, lb_pair};
+    use crate::manager::bin_array_manager::BinArrayManager;
+    use std::cell::{Ref, RefCell, RefMut};
+
+    fn quote(
+        lb_pair: &LbPair,
+        bin_arrays: &[RefCell<BinArray>],
+        amount_in: u64,
+        swap_for_y: bool,
+        fee_multiplier_bps: u16,
+    ) -> SwapQuote {
+        let mut lb_pair = *lb_pair;
+        let bin_arrays = bin_arrays
+            .iter()
+            .map(RefCell::borrow)
+            .collect::<Vec<Ref<BinArray>>>();
+        let mut bin_array_manager = BinArrayManagerSimulation::new(&bin_arrays).unwrap();
+
+        simulate_swap_exact_in(
+            &mut lb_pair,
+            &mut bin_array_manager,
+            &None,
+            amount_in,
+            swap_for_y,
+            fee_multiplier_bps,
+            0,
+            0,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_simulate_swap_exact_in_match_swap() {
+        let lb_pair = lb_pair(&[-1, 0]);
+        let bin_arrays = [
+            RefCell::new(bin_array(0, 0, 1_000_000)),
+            RefCell::new(bin_array(-1, 0, 1_000_000)),
+        ];
+
+        let swap_quote = quote(
+            &lb_pair,
+            &bin_arrays,
+            5_000_000,
+            true,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        );
+
+        // Simulation do not mutate the bin arrays
+        assert!(bin_arrays.iter().all(|bin_array| bin_array
+            .borrow()
+            .bins
+            .iter()
+            .all(|bin| bin.amount_x == 0)));
+
+        let mut lb_pair = lb_pair;
+        lb_pair.update_protocol_share(0);
+        lb_pair.update_references(0).unwrap();
+        let fee_modifier = lb_pair.get_swap_fee_modifier(0, true, NO_FEE_DISCOUNT_MULTIPLIER_BPS);
+
+        let mut bin_arrays = bin_arrays
+            .iter()
+            .map(RefCell::borrow_mut)
+            .collect::<Vec<RefMut<BinArray>>>();
+        let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays).unwrap();
+
+        let total = swap_bins(
+            &mut lb_pair,
+            &mut bin_array_manager,
+            &None,
+            true,
+            5_000_000,
+            None,
+            None,
+            None,
+            &fee_modifier,
+        )
+        .unwrap();
+
+        assert_eq!(swap_quote.amount_in, total.amount_in);
+        assert_eq!(swap_quote.amount_out, total.amount_out);
+        assert_eq!(swap_quote.fee, total.fee);
+        assert_eq!(swap_quote.protocol_fee, total.protocol_fee);
+        assert_eq!(swap_quote.end_bin_id, total.end_bin_id);
+        // Crossed into the next bin array
+        assert!(swap_quote.end_bin_id < 0);
+        assert_eq!(swap_quote.amount_in, 5_000_000);
+    }
+
+    #[test]
+    fn test_simulate_swap_exact_in_apply_fee_tier_discount() {
+        let lb_pair = lb_pair(&[0]);
+        let bin_arrays = [RefCell::new(bin_array(0, 1_000_000, 0))];
+
+        let base_quote = quote(
+            &lb_pair,
+            &bin_arrays,
+            100_000,
+            false,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        );
+        let discounted_quote = quote(&lb_pair, &bin_arrays, 100_000, false, 5_000);
+
+        assert!(base_quote.fee > 0);
+        assert_eq!(discounted_quote.fee, base_quote.fee / 2);
+        assert!(discounted_quote.amount_out > base_quote.amount_out);
+    }
+
+    #[test]
+    fn test_simulate_swap_exact_in_skip_empty_bin_arrays() {
+        // Bin array 0, and -1 have no liquidity. Active bin jump to the upper bin of bin array -2.
+        let lb_pair = lb_pair(&[-2]);
+        let bin_arrays = [RefCell::new(bin_array(-2, 0, 1_000_000))];
+
+        let swap_quote = quote(
+            &lb_pair,
+            &bin_arrays,
+            1_000,
+            true,
+            NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        );
+
+        let (_, upper_bin_id) = BinArray::get_bin_array_lower_upper_bin_id(-2).unwrap();
+        assert_eq!(swap_quote.end_bin_id, upper_bin_id);
+        assert!(swap_quote.amount_out > 0);
+    }
+}
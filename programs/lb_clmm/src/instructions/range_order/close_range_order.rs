@@ -0,0 +1,243 @@
+use crate::errors::LBError;
+use crate::events::CloseRangeOrder as CloseRangeOrderEvent;
+use crate::instructions::position_authorize::PositionLiquidityFlowValidator;
+use crate::instructions::withdraw::remove_liquidity::withdraw_all_position_liquidity;
+use crate::manager::bin_array_manager::BinArrayManager;
+use crate::math::safe_math::SafeMath;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
+use crate::state::position::{PositionV2, RangeOrderSide};
+use crate::state::{bin::BinArray, lb_pair::LbPair};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseRangeOrder<'info> {
+    #[account(
+        mut,
+        has_one = lb_pair,
+        has_one = owner,
+    )]
+    pub position: AccountLoader<'info, PositionV2>,
+
+    #[account(
+        mut,
+        has_one = reserve_x,
+        has_one = reserve_y,
+        has_one = token_x_mint,
+        has_one = token_y_mint,
+    )]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        mut,
+        has_one = lb_pair,
+    )]
+    pub bin_array_bitmap_extension: Option<AccountLoader<'info, BinArrayBitmapExtension>>,
+
+    #[account(
+        mut,
+        has_one = lb_pair
+    )]
+    pub bin_array_lower: AccountLoader<'info, BinArray>,
+    #[account(
+        mut,
+        has_one = lb_pair
+    )]
+    pub bin_array_upper: AccountLoader<'info, BinArray>,
+
+    #[account(mut)]
+    pub reserve_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub reserve_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Position owner. Receive the converted tokens, and fees.
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Payer of the position rental. Receive the rental when the position is closed.
+    #[account(
+        mut,
+        constraint = position.load()?.get_rent_payer() == rent_payer.key() @ LBError::WrongRentReceiver
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_x_mint
+    )]
+    pub owner_token_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_y_mint
+    )]
+    pub owner_token_y: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_x_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_y_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub sender: Signer<'info>,
+    pub token_x_program: Interface<'info, TokenInterface>,
+    pub token_y_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CloseRangeOrder<'info> {
+    fn transfer_from_lb_pair(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program,
+                TransferChecked {
+                    from,
+                    to,
+                    authority: self.lb_pair.to_account_info(),
+                    mint: mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+}
+
+impl<'info> PositionLiquidityFlowValidator for CloseRangeOrder<'info> {
+    fn validate_outflow_to_ata_of_position_owner(&self, owner: Pubkey) -> Result<()> {
+        let owner_ata_x = get_associated_token_address_with_program_id(
+            &owner,
+            &self.token_x_mint.key(),
+            &self.token_x_program.key(),
+        );
+        let owner_ata_y = get_associated_token_address_with_program_id(
+            &owner,
+            &self.token_y_mint.key(),
+            &self.token_y_program.key(),
+        );
+
+        require!(
+            owner_ata_x == self.owner_token_x.key() && owner_ata_y == self.owner_token_y.key(),
+            LBError::WithdrawToWrongTokenAccount
+        );
+
+        Ok(())
+    }
+}
+
+/// Permissionless. Close the range order once the active bin has fully crossed the position range. All liquidity is withdrawn, and the converted tokens, and fees go to the owner. The position rental go to the rent payer.
+/// Rewards are not transferred by the crank. Position with pending rewards is kept open, without the range order flag, until the owner claim the rewards and close it.
+pub fn handle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CloseRangeOrder<'info>>,
+) -> Result<()> {
+    ctx.accounts
+        .validate_outflow_to_ata_of_position_owner(ctx.accounts.owner.key())?;
+
+    let (amount_x, amount_y, fee_x, fee_y, is_closed) = {
+        let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+        let mut position = ctx.accounts.position.load_mut()?;
+
+        let range_order_side = position.range_order_side()?;
+        require!(
+            range_order_side != RangeOrderSide::None,
+            LBError::InvalidRangeOrder
+        );
+
+        let current_point = get_lb_pair_type_access_validator(&lb_pair)?.get_current_point();
+        require!(
+            !position.is_liquidity_locked(current_point),
+            LBError::LiquidityLocked
+        );
+
+        require!(
+            position.is_range_order_crossed(lb_pair.active_id)?,
+            LBError::RangeOrderNotFilled
+        );
+
+        let mut bin_arrays = [
+            ctx.accounts.bin_array_lower.load_mut()?,
+            ctx.accounts.bin_array_upper.load_mut()?,
+        ];
+        let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays)?;
+
+        let (amount_x, amount_y) = withdraw_all_position_liquidity(
+            &mut lb_pair,
+            &mut position,
+            &mut bin_array_manager,
+            &ctx.accounts.bin_array_bitmap_extension,
+        )?;
+
+        // Every bin must be converted to the other token
+        let unconverted_amount = match range_order_side {
+            RangeOrderSide::Ask => amount_x,
+            _ => amount_y,
+        };
+        require!(unconverted_amount == 0, LBError::RangeOrderNotFilled);
+
+        let (fee_x, fee_y) = position.claim_fee()?;
+        position.accumulate_total_claimed_fees(fee_x, fee_y);
+
+        position.range_order_side = RangeOrderSide::None.into();
+
+        let is_closed = position.is_empty();
+        if is_closed {
+            lb_pair.decrease_position_count();
+        }
+
+        (amount_x, amount_y, fee_x, fee_y, is_closed)
+    };
+
+    let lb_pair_seeds = ctx.accounts.lb_pair.load()?.signer_seeds()?;
+    let lb_pair_seeds = lb_pair_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect::<Vec<&[u8]>>();
+    let signer_seeds = &[&lb_pair_seeds[..]];
+
+    let accounts = &ctx.accounts;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_x.to_account_info(),
+        accounts.owner_token_x.to_account_info(),
+        &accounts.token_x_mint,
+        accounts.token_x_program.to_account_info(),
+        signer_seeds,
+        amount_x.safe_add(fee_x)?,
+    )?;
+    accounts.transfer_from_lb_pair(
+        accounts.reserve_y.to_account_info(),
+        accounts.owner_token_y.to_account_info(),
+        &accounts.token_y_mint,
+        accounts.token_y_program.to_account_info(),
+        signer_seeds,
+        amount_y.safe_add(fee_y)?,
+    )?;
+
+    emit_cpi!(CloseRangeOrderEvent {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        amounts: [amount_x, amount_y],
+        fees: [fee_x, fee_y],
+        is_closed,
+    });
+
+    if is_closed {
+        ctx.accounts
+            .position
+            .close(ctx.accounts.rent_payer.to_account_info())?;
+    }
+
+    Ok(())
+}
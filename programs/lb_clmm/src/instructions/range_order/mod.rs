@@ -0,0 +1,3 @@
+pub mod close_range_order;
+
+pub use close_range_order::*;
@@ -0,0 +1,32 @@
+use crate::instructions::initialize_whitelisted_wallet::WhitelistedWalletParameter;
+use crate::state::lb_pair::LbPair;
+use crate::state::whitelisted_wallet::WhitelistedWallet;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RefreshWhitelistedWallet<'info> {
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    #[account(
+        mut,
+        has_one = lb_pair,
+        has_one = wallet,
+    )]
+    pub whitelisted_wallet: AccountLoader<'info, WhitelistedWallet>,
+
+    pub wallet: Signer<'info>,
+}
+
+/// Re-prove the whitelisted wallet against the current merkle root of the pair, after the root was rotated.
+pub fn handle(
+    ctx: Context<RefreshWhitelistedWallet>,
+    parameter: WhitelistedWalletParameter,
+) -> Result<()> {
+    let lb_pair = ctx.accounts.lb_pair.load()?;
+    parameter.verify(&lb_pair, ctx.accounts.wallet.key())?;
+
+    let mut whitelisted_wallet = ctx.accounts.whitelisted_wallet.load_mut()?;
+    whitelisted_wallet.update(lb_pair.whitelist_merkle_root, &parameter);
+
+    Ok(())
+}
@@ -1,9 +1,56 @@
+use crate::constants::{HOST_FEE_BPS, NO_FEE_DISCOUNT_MULTIPLIER_BPS};
 use crate::errors::LBError;
+use crate::events::Swap as SwapEvent;
+use crate::manager::bin_array_manager::{BinArrayManager, SwapBinSource};
+use crate::math::price_math::get_price_impact_limit_bin_id;
+use crate::math::safe_math::SafeMath;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::bin::{BinArray, SwapResult};
 use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
+use crate::state::fee_tier::load_fee_tier;
 use crate::state::lb_pair::*;
-use crate::state::oracle::Oracle;
+use crate::state::oracle::{Oracle, OracleContentLoader};
+use crate::state::whitelisted_wallet::load_whitelisted_wallet;
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Eq, PartialEq, Clone, Debug)]
+pub struct SwapGuard {
+    /// Slot or timestamp, based on the activation type of the pair. The swap is rejected once the current point passed the deadline.
+    pub deadline: u64,
+    /// Active bin that integrator observe off-chain
+    pub active_id: i32,
+    /// max active bin slippage allowed
+    pub max_active_bin_slippage: i32,
+}
+
+impl SwapGuard {
+    pub fn validate(&self, lb_pair: &LbPair) -> Result<()> {
+        let current_point = get_lb_pair_type_access_validator(lb_pair)?.get_current_point();
+        self.validate_at(lb_pair.active_id, current_point)
+    }
+
+    /// Validate against the pair active bin, and the current point of the pair activation type
+    pub fn validate_at(&self, active_id: i32, current_point: u64) -> Result<()> {
+        require!(
+            current_point <= self.deadline,
+            LBError::SwapDeadlineExceeded
+        );
+
+        let bin_shift = i64::from(active_id)
+            .safe_sub(self.active_id.into())?
+            .unsigned_abs();
+
+        require!(
+            self.max_active_bin_slippage >= 0 && bin_shift <= self.max_active_bin_slippage as u64,
+            LBError::ExceededBinSlippageTolerance
+        );
+
+        Ok(())
+    }
+}
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -54,26 +101,592 @@ pub struct Swap<'info> {
     pub token_y_program: Interface<'info, TokenInterface>,
 }
 
+impl<'info> Swap<'info> {
+    fn transfer_to_lb_pair(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new(
+                token_program,
+                TransferChecked {
+                    from,
+                    to,
+                    authority: self.user.to_account_info(),
+                    mint: mint.to_account_info(),
+                },
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+
+    fn transfer_from_lb_pair(
+        &self,
+        from: AccountInfo<'info>,
+        to: AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program,
+                TransferChecked {
+                    from,
+                    to,
+                    authority: self.lb_pair.to_account_info(),
+                    mint: mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+}
+
+/// Accumulated result of the swap over the crossed bins
+#[derive(Debug, Default)]
+pub struct SwapTotal {
+    pub swap_for_y: bool,
+    pub start_bin_id: i32,
+    pub end_bin_id: i32,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub fee_bps: u128,
+}
+
+/// Swap across the bins starting from the active bin of the pair. Shared by the swap instructions, and quote_swap, so that the quote step through the bins the same way as the swap.
+/// Swap exact in when exact_out_amount is None, else swap until exact_out_amount is reached. The fee modifier must be computed after the references of the pair are updated.
+/// When limit_bin_id is set, the swap stop once the active bin reach limit_bin_id, which is not swapped. The amount in left is not consumed.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_bins<B: SwapBinSource>(
+    lb_pair: &mut LbPair,
+    bins: &mut B,
+    bin_array_bitmap_extension: &Option<AccountLoader<BinArrayBitmapExtension>>,
+    swap_for_y: bool,
+    amount_in: u64,
+    exact_out_amount: Option<u64>,
+    limit_bin_id: Option<i32>,
+    host_fee_bps: Option<u16>,
+    fee_modifier: &SwapFeeModifier,
+) -> Result<SwapTotal> {
+    let is_limit_reached = |bin_id: i32| match limit_bin_id {
+        Some(limit_bin_id) if swap_for_y => bin_id <= limit_bin_id,
+        Some(limit_bin_id) => bin_id >= limit_bin_id,
+        None => false,
+    };
+
+    let mut total = SwapTotal {
+        swap_for_y,
+        start_bin_id: lb_pair.active_id,
+        ..Default::default()
+    };
+
+    // Swap exact out is bounded by the out amount. The in amount is checked against the maximum in amount once the swap is done.
+    let mut amount_in_left = match exact_out_amount {
+        Some(_) => u64::MAX,
+        None => amount_in,
+    };
+    let mut amount_out_left = exact_out_amount.unwrap_or(u64::MAX);
+
+    while amount_in_left > 0 && amount_out_left > 0 && !is_limit_reached(lb_pair.active_id) {
+        let active_id = lb_pair.active_id;
+        lb_pair.next_bin_array_index_with_liquidity(swap_for_y, bin_array_bitmap_extension)?;
+
+        // Bin arrays without liquidity in between were skipped. Stay if the next bin with liquidity is beyond the limit.
+        if is_limit_reached(lb_pair.active_id) {
+            lb_pair.active_id = active_id;
+            break;
+        }
+
+        let bin_array_index = BinArray::bin_id_to_bin_array_index(lb_pair.active_id)?;
+
+        while amount_in_left > 0
+            && amount_out_left > 0
+            && !is_limit_reached(lb_pair.active_id)
+            && BinArray::bin_id_to_bin_array_index(lb_pair.active_id)? == bin_array_index
+        {
+            lb_pair.update_volatility_accumulator()?;
+
+            let active_id = lb_pair.active_id;
+            let active_bin = bins.get_bin_mut(active_id)?;
+            let price = active_bin.get_or_store_bin_price(active_id, lb_pair.bin_step)?;
+
+            if !active_bin.is_empty(!swap_for_y) {
+                let SwapResult {
+                    amount_in_with_fees,
+                    amount_out,
+                    fee,
+                    protocol_fee_after_host_fee,
+                    host_fee,
+                    ..
+                } = match exact_out_amount {
+                    Some(_) => active_bin.swap_exact_out(
+                        amount_in_left,
+                        price,
+                        swap_for_y,
+                        lb_pair,
+                        host_fee_bps,
+                        fee_modifier,
+                        amount_out_left,
+                    )?,
+                    None => active_bin.swap(
+                        amount_in_left,
+                        price,
+                        swap_for_y,
+                        lb_pair,
+                        host_fee_bps,
+                        fee_modifier,
+                    )?,
+                };
+
+                let lp_fee = fee
+                    .safe_sub(protocol_fee_after_host_fee)?
+                    .safe_sub(host_fee)?;
+                active_bin.update_fee_per_token_stored(lp_fee, swap_for_y)?;
+
+                if swap_for_y {
+                    lb_pair.accumulate_protocol_fees(protocol_fee_after_host_fee, 0)?;
+                } else {
+                    lb_pair.accumulate_protocol_fees(0, protocol_fee_after_host_fee)?;
+                }
+
+                amount_in_left = amount_in_left.safe_sub(amount_in_with_fees)?;
+                amount_out_left = amount_out_left.saturating_sub(amount_out);
+
+                total.amount_in = total.amount_in.safe_add(amount_in_with_fees)?;
+                total.amount_out = total.amount_out.safe_add(amount_out)?;
+                total.fee = total.fee.safe_add(fee)?;
+                total.protocol_fee = total.protocol_fee.safe_add(protocol_fee_after_host_fee)?;
+                total.host_fee = total.host_fee.safe_add(host_fee)?;
+            }
+
+            if amount_in_left > 0 && amount_out_left > 0 {
+                lb_pair.advance_active_bin(swap_for_y)?;
+            }
+        }
+    }
+
+    if exact_out_amount.is_some() {
+        require!(
+            total.amount_in <= amount_in,
+            LBError::ExceededAmountSlippageTolerance
+        );
+    }
+
+    total.end_bin_id = lb_pair.active_id;
+    total.fee_bps = lb_pair.get_swap_total_fee(fee_modifier)?;
+
+    Ok(total)
+}
+
+/// Swap through the bin arrays passed in remaining accounts. The pair, and bin arrays are updated, while the tokens are settled by settle_swap.
+/// Swap exact in when exact_out_amount is None, else swap until exact_out_amount is reached with amount_in as the maximum in amount. The swap stop before limit_bin_id when it is set.
+fn swap<'a, 'b, 'c, 'info>(
+    ctx: &Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    amount_in: u64,
+    exact_out_amount: Option<u64>,
+    limit_bin_id: Option<i32>,
+) -> Result<SwapTotal> {
+    require!(
+        amount_in > 0 && exact_out_amount.map_or(true, |amount| amount > 0),
+        LBError::InvalidInput
+    );
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let user = ctx.accounts.user.key();
+    let mut remaining_accounts = ctx.remaining_accounts;
+
+    let whitelisted_wallet = load_whitelisted_wallet(&mut remaining_accounts, lb_pair_key, user)?;
+    let mut whitelisted_wallet = whitelisted_wallet
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+
+    let fee_multiplier_bps = match load_fee_tier(&mut remaining_accounts, lb_pair_key, user)? {
+        Some(fee_tier) => fee_tier.load()?.get_fee_multiplier_bps(),
+        None => NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+    };
+
+    let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+
+    let (current_point, is_in_pre_activation_window) = {
+        let pair_type_access_validator = get_lb_pair_type_access_validator(&lb_pair)?;
+        require!(
+            pair_type_access_validator.validate_swap_access(user, whitelisted_wallet.as_deref()),
+            LBError::PoolDisabled
+        );
+        (
+            pair_type_access_validator.get_current_point(),
+            pair_type_access_validator.is_in_pre_activation_window(),
+        )
+    };
+
+    if let Some(host_fee_in) = &ctx.accounts.host_fee_in {
+        require!(
+            host_fee_in.mint == ctx.accounts.user_token_in.mint,
+            LBError::InvalidTokenMint
+        );
+    }
+    let host_fee_bps = ctx.accounts.host_fee_in.as_ref().map(|_| HOST_FEE_BPS);
+
+    let swap_for_y = lb_pair.swap_for_y(ctx.accounts.user_token_out.mint);
+
+    let bin_array_loaders = remaining_accounts
+        .iter()
+        .map(AccountLoader::<BinArray>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut bin_arrays = bin_array_loaders
+        .iter()
+        .map(|loader| loader.load_mut())
+        .collect::<Result<Vec<_>>>()?;
+
+    for bin_array in bin_arrays.iter() {
+        require!(bin_array.lb_pair == lb_pair_key, LBError::InvalidBinArray);
+    }
+
+    let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays)?;
+    let clock = Clock::get()?;
+
+    lb_pair.update_protocol_share(current_point);
+    lb_pair.update_references(clock.unix_timestamp)?;
+    bin_array_manager.update_rewards(&mut lb_pair)?;
+
+    ctx.accounts
+        .oracle
+        .load_content_mut()?
+        .update(lb_pair.active_id, clock.unix_timestamp)?;
+
+    let fee_modifier = lb_pair.get_swap_fee_modifier(clock.slot, swap_for_y, fee_multiplier_bps);
+
+    let total = swap_bins(
+        &mut lb_pair,
+        &mut bin_array_manager,
+        &ctx.accounts.bin_array_bitmap_extension,
+        swap_for_y,
+        amount_in,
+        exact_out_amount,
+        limit_bin_id,
+        host_fee_bps,
+        &fee_modifier,
+    )?;
+
+    // Whitelisted wallet is capped during the pre-activation window only
+    if is_in_pre_activation_window {
+        if let Some(whitelisted_wallet) = whitelisted_wallet.as_mut() {
+            whitelisted_wallet.accumulate_swap_amount(swap_for_y, total.amount_in)?;
+        }
+    }
+
+    lb_pair.v_parameters.last_update_timestamp = clock.unix_timestamp;
+    lb_pair.record_slot_swap(clock.slot, swap_for_y, total.amount_in, total.amount_out)?;
+
+    Ok(total)
+}
+
+/// Transfer the swapped tokens between the user, and the reserves. Host fee is transferred from the user to the host directly.
+fn settle_swap<'a, 'b, 'c, 'info>(
+    ctx: &Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    total: &SwapTotal,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+
+    let (reserve_in, reserve_out, mint_in, mint_out, token_program_in, token_program_out) =
+        if total.swap_for_y {
+            (
+                &accounts.reserve_x,
+                &accounts.reserve_y,
+                &accounts.token_x_mint,
+                &accounts.token_y_mint,
+                &accounts.token_x_program,
+                &accounts.token_y_program,
+            )
+        } else {
+            (
+                &accounts.reserve_y,
+                &accounts.reserve_x,
+                &accounts.token_y_mint,
+                &accounts.token_x_mint,
+                &accounts.token_y_program,
+                &accounts.token_x_program,
+            )
+        };
+
+    accounts.transfer_to_lb_pair(
+        accounts.user_token_in.to_account_info(),
+        reserve_in.to_account_info(),
+        mint_in,
+        token_program_in.to_account_info(),
+        total.amount_in.safe_sub(total.host_fee)?,
+    )?;
+
+    if let Some(host_fee_in) = &accounts.host_fee_in {
+        accounts.transfer_to_lb_pair(
+            accounts.user_token_in.to_account_info(),
+            host_fee_in.to_account_info(),
+            mint_in,
+            token_program_in.to_account_info(),
+            total.host_fee,
+        )?;
+    }
+
+    let lb_pair_seeds = accounts.lb_pair.load()?.signer_seeds()?;
+    let lb_pair_seeds = lb_pair_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect::<Vec<&[u8]>>();
+
+    accounts.transfer_from_lb_pair(
+        reserve_out.to_account_info(),
+        accounts.user_token_out.to_account_info(),
+        mint_out,
+        token_program_out.to_account_info(),
+        &[&lb_pair_seeds[..]],
+        total.amount_out,
+    )?;
+
+    emit_cpi!(SwapEvent {
+        lb_pair: accounts.lb_pair.key(),
+        from: accounts.user.key(),
+        start_bin_id: total.start_bin_id,
+        end_bin_id: total.end_bin_id,
+        amount_in: total.amount_in,
+        amount_out: total.amount_out,
+        swap_for_y: total.swap_for_y,
+        fee: total.fee,
+        protocol_fee: total.protocol_fee,
+        fee_bps: total.fee_bps,
+        host_fee: total.host_fee,
+    });
+
+    Ok(())
+}
+
+/// Whitelisted wallet, and fee tier of the user, if any, are passed as the first remaining accounts in that order. Followed by the bin arrays required by the swap, ordered in the swap direction.
 pub fn handle_exact_in<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
     amount_in: u64,
     min_amount_out: u64,
 ) -> Result<()> {
-    Ok(())
+    let total = swap(&ctx, amount_in, None, None)?;
+    require!(
+        total.amount_out >= min_amount_out,
+        LBError::ExceededAmountSlippageTolerance
+    );
+
+    settle_swap(&ctx, &total)
 }
 
+/// Whitelisted wallet, and fee tier of the user, if any, are passed as the first remaining accounts in that order. Followed by the bin arrays required by the swap, ordered in the swap direction.
 pub fn handle_exact_out<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
     max_in_amount: u64,
     exact_out_amount: u64,
 ) -> Result<()> {
-    Ok(())
+    let total = swap(&ctx, max_in_amount, Some(exact_out_amount), None)?;
+    require!(
+        total.amount_out >= exact_out_amount,
+        LBError::InsufficientOutAmount
+    );
+
+    settle_swap(&ctx, &total)
 }
+
+/// Whitelisted wallet, and fee tier of the user, if any, are passed as the first remaining accounts in that order. Followed by the bin arrays required by the swap, ordered in the swap direction.
+/// Swap exact in until the price move beyond max_price_impact_bps from the price of active_id, or the pair active bin when None. Only the consumed amount in is transferred from the user.
 pub fn handle_exact_in_with_price_impact<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
     amount_in: u64,
     active_id: Option<i32>,
     max_price_impact_bps: u16,
 ) -> Result<()> {
-    Ok(())
+    let limit_bin_id = {
+        let lb_pair = ctx.accounts.lb_pair.load()?;
+        get_price_impact_limit_bin_id(
+            active_id.unwrap_or(lb_pair.active_id),
+            lb_pair.bin_step,
+            lb_pair.swap_for_y(ctx.accounts.user_token_out.mint),
+            max_price_impact_bps,
+        )?
+    };
+
+    let total = swap(&ctx, amount_in, None, Some(limit_bin_id))?;
+
+    settle_swap(&ctx, &total)
+}
+
+pub fn handle_exact_in_guarded<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    guard: SwapGuard,
+) -> Result<()> {
+    {
+        let lb_pair = ctx.accounts.lb_pair.load()?;
+        guard.validate(&lb_pair)?;
+    }
+    handle_exact_in(ctx, amount_in, min_amount_out)
+}
+
+pub fn handle_exact_out_guarded<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    max_in_amount: u64,
+    exact_out_amount: u64,
+    guard: SwapGuard,
+) -> Result<()> {
+    {
+        let lb_pair = ctx.accounts.lb_pair.load()?;
+        guard.validate(&lb_pair)?;
+    }
+    handle_exact_out(ctx, max_in_amount, exact_out_amount)
+}
+
+pub fn handle_exact_in_with_price_impact_guarded<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    amount_in: u64,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+    guard: SwapGuard,
+) -> Result<()> {
+    {
+        let lb_pair = ctx.accounts.lb_pair.load()?;
+        guard.validate(&lb_pair)?;
+    }
+    handle_exact_in_with_price_impact(ctx, amount_in, active_id, max_price_impact_bps)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::math::u64x64_math::SCALE_OFFSET;
+    use std::cell::{RefCell, RefMut};
+
+    /// Pair with bin step 10 at active bin 0, and liquidity flagged on the bin arrays of bin_array_indexes
+    pub fn lb_pair(bin_array_indexes: &[i32]) -> LbPair {
+        let mut lb_pair = LbPair::default();
+        lb_pair.bin_step = 10;
+        lb_pair.parameters.base_factor = 10_000;
+        lb_pair.parameters.protocol_share = 1_000;
+        for &index in bin_array_indexes {
+            lb_pair.flip_bin_array_bit(&None, index).unwrap();
+        }
+        lb_pair
+    }
+
+    /// Bin array with the same amounts in every bin
+    pub fn bin_array(index: i64, amount_x: u64, amount_y: u64) -> BinArray {
+        let mut bin_array: BinArray = bytemuck::Zeroable::zeroed();
+        bin_array.index = index;
+        for bin in bin_array.bins.iter_mut() {
+            bin.amount_x = amount_x;
+            bin.amount_y = amount_y;
+            bin.liquidity_supply = 1u128 << SCALE_OFFSET;
+        }
+        bin_array
+    }
+
+    fn swap_until(
+        lb_pair: &mut LbPair,
+        bin_arrays: &[RefCell<BinArray>],
+        amount_in: u64,
+        swap_for_y: bool,
+        limit_bin_id: i32,
+    ) -> SwapTotal {
+        let mut bin_arrays = bin_arrays
+            .iter()
+            .map(RefCell::borrow_mut)
+            .collect::<Vec<RefMut<BinArray>>>();
+        let mut bin_array_manager = BinArrayManager::new(&mut bin_arrays).unwrap();
+
+        swap_bins(
+            lb_pair,
+            &mut bin_array_manager,
+            &None,
+            swap_for_y,
+            amount_in,
+            None,
+            Some(limit_bin_id),
+            None,
+            &SwapFeeModifier::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_swap_guard_validate_at() {
+        let guard = SwapGuard {
+            deadline: 100,
+            active_id: 10,
+            max_active_bin_slippage: 3,
+        };
+
+        assert!(guard.validate_at(10, 100).is_ok());
+        assert!(guard.validate_at(13, 0).is_ok());
+        assert!(guard.validate_at(7, 0).is_ok());
+
+        let err = guard.validate_at(10, 101).unwrap_err();
+        assert_eq!(err, LBError::SwapDeadlineExceeded.into());
+
+        let err = guard.validate_at(14, 0).unwrap_err();
+        assert_eq!(err, LBError::ExceededBinSlippageTolerance.into());
+        let err = guard.validate_at(6, 0).unwrap_err();
+        assert_eq!(err, LBError::ExceededBinSlippageTolerance.into());
+
+        let guard = SwapGuard {
+            max_active_bin_slippage: -1,
+            ..guard
+        };
+        let err = guard.validate_at(10, 0).unwrap_err();
+        assert_eq!(err, LBError::ExceededBinSlippageTolerance.into());
+    }
+
+    #[test]
+    fn test_swap_bins_stop_before_limit_bin_id() {
+        let mut lb_pair = lb_pair(&[0]);
+        let bin_arrays = [RefCell::new(bin_array(0, 1_000_000, 0))];
+
+        let total = swap_until(&mut lb_pair, &bin_arrays, 100_000_000, false, 3);
+
+        // Bin 0, 1, and 2 are swapped out
+        assert_eq!(total.end_bin_id, 3);
+        assert_eq!(total.amount_out, 3_000_000);
+        assert!(total.amount_in < 100_000_000);
+
+        let bin_arrays = bin_arrays[0].borrow();
+        assert_eq!(bin_arrays.get_bin(2).unwrap().amount_x, 0);
+        assert_eq!(bin_arrays.get_bin(3).unwrap().amount_x, 1_000_000);
+    }
+
+    #[test]
+    fn test_swap_bins_do_not_jump_beyond_limit_bin_id() {
+        // Bin array 0, and -1 have no liquidity. The next bin with liquidity is beyond the limit.
+        let mut lb_pair = lb_pair(&[-2]);
+        let bin_arrays = [RefCell::new(bin_array(-2, 0, 1_000_000))];
+
+        let total = swap_until(&mut lb_pair, &bin_arrays, 1_000, true, -50);
+
+        assert_eq!(total.amount_in, 0);
+        assert_eq!(total.amount_out, 0);
+        assert_eq!(total.end_bin_id, 0);
+        assert_eq!(lb_pair.active_id, 0);
+    }
 }
@@ -0,0 +1,60 @@
+use crate::errors::LBError;
+use crate::events::PresetParameterSync;
+use crate::state::lb_pair::{LbPair, PairType};
+use crate::state::preset_parameters::PresetParameter;
+use crate::utils::pda::derive_preset_parameter_pda2;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SyncPairToPreset<'info> {
+    #[account(mut)]
+    pub lb_pair: AccountLoader<'info, LbPair>,
+
+    pub preset_parameter: Account<'info, PresetParameter>,
+}
+
+/// Permissionless crank. Apply the latest static parameters of the preset parameter to the pair created from it.
+/// Only Permissionless pair with require_base_factor_seed is eligible, as the preset parameter is derived from its bin step and base factor seed. Pair with fee parameters updated by the admin is rejected to keep the override.
+/// Protocol share, step down schedule, and volatility surcharge of the pair are retained.
+pub fn handle(ctx: Context<SyncPairToPreset>) -> Result<()> {
+    let mut lb_pair = ctx.accounts.lb_pair.load_mut()?;
+    let preset_parameter = &ctx.accounts.preset_parameter;
+
+    require!(
+        lb_pair.pair_type == Into::<u8>::into(PairType::Permissionless)
+            && lb_pair.require_base_factor_seed != 0,
+        LBError::InvalidPoolType
+    );
+
+    let (preset_parameter_key, _bump) = derive_preset_parameter_pda2(
+        lb_pair.bin_step,
+        u16::from_le_bytes(lb_pair.base_factor_seed),
+    );
+    require!(
+        preset_parameter_key == preset_parameter.key(),
+        LBError::InvalidInput
+    );
+
+    // last_updated_at is only set by update_fee_parameters
+    require!(
+        lb_pair.last_updated_at == 0,
+        LBError::FeeParametersOverridden
+    );
+
+    let version = preset_parameter.version();
+    require!(
+        lb_pair.preset_parameter_version < version,
+        LBError::InvalidInput
+    );
+
+    lb_pair.sync_to_preset_parameter(preset_parameter, version);
+
+    emit_cpi!(PresetParameterSync {
+        lb_pair: ctx.accounts.lb_pair.key(),
+        preset_parameter: preset_parameter.key(),
+        version,
+    });
+
+    Ok(())
+}
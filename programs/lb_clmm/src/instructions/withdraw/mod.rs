@@ -1,5 +1,7 @@
 pub mod remove_all_liquidity;
 pub mod remove_liquidity;
+pub mod remove_liquidity_by_range;
 
 pub use remove_all_liquidity::*;
 pub use remove_liquidity::*;
+pub use remove_liquidity_by_range::*;
@@ -1,8 +1,14 @@
 use crate::constants::BASIS_POINT_MAX;
+use crate::manager::bin_array_manager::BinArrayManager;
+use crate::pair_action_access::get_lb_pair_type_access_validator;
+use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
+use crate::state::lb_pair::LbPair;
 use crate::ModifyLiquidity;
 use crate::{errors::LBError, math::safe_math::SafeMath, state::position::PositionV2};
 use anchor_lang::prelude::*;
+use num_traits::Zero;
 use ruint::aliases::U256;
+use std::cell::RefMut;
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
 pub struct BinLiquidityReduction {
     pub bin_id: i32,
@@ -20,6 +26,60 @@ pub fn calculate_shares_to_remove(bps: u16, bin_id: i32, position: &PositionV2)
     Ok(share_to_remove)
 }
 
+/// Withdraw all liquidity of the position from the bins. Fees, and rewards are settled into the position before the liquidity share changes, and remain claimable. Bin arrays emptied by the withdrawal are flipped off the bin array bitmap.
+pub fn withdraw_all_position_liquidity<'info>(
+    lb_pair: &mut RefMut<'_, LbPair>,
+    position: &mut PositionV2,
+    bin_array_manager: &mut BinArrayManager,
+    bin_array_bitmap_extension: &Option<AccountLoader<'info, BinArrayBitmapExtension>>,
+) -> Result<(u64, u64)> {
+    bin_array_manager.validate_bin_arrays(position.lower_bin_id)?;
+    bin_array_manager.update_rewards(lb_pair)?;
+    position.update_earning_per_token_stored(bin_array_manager)?;
+
+    let before_zero_liquidity_flags = bin_array_manager.get_zero_liquidity_flags();
+    let mut amount_x: u64 = 0;
+    let mut amount_y: u64 = 0;
+
+    let pair_type_access_validator = get_lb_pair_type_access_validator(lb_pair)?;
+    for bin_id in position.lower_bin_id..=position.upper_bin_id {
+        let liquidity_share = position.get_liquidity_share_in_bin(bin_id)?;
+        if liquidity_share.is_zero() {
+            continue;
+        }
+
+        require!(
+            pair_type_access_validator
+                .validate_remove_liquidity_access(bin_id > lb_pair.active_id)?,
+            LBError::LiquidityLocked
+        );
+
+        let bin = bin_array_manager.get_bin_mut(bin_id)?;
+        let (out_amount_x, out_amount_y) = bin.withdraw(liquidity_share)?;
+        position.withdraw(bin_id, liquidity_share)?;
+
+        amount_x = amount_x.safe_add(out_amount_x)?;
+        amount_y = amount_y.safe_add(out_amount_y)?;
+    }
+    drop(pair_type_access_validator);
+
+    let after_zero_liquidity_flags = bin_array_manager.get_zero_liquidity_flags();
+    for (i, (before, after)) in before_zero_liquidity_flags
+        .iter()
+        .zip(after_zero_liquidity_flags.iter())
+        .enumerate()
+    {
+        if !before && *after {
+            lb_pair.flip_bin_array_bit(
+                bin_array_bitmap_extension,
+                bin_array_manager.get_bin_array_index(i)?,
+            )?;
+        }
+    }
+
+    Ok((amount_x, amount_y))
+}
+
 pub fn handle<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
     bin_liquidity_reduction: Vec<BinLiquidityReduction>,
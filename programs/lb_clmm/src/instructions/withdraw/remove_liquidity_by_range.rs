@@ -0,0 +1,53 @@
+use super::remove_liquidity::BinLiquidityReduction;
+use crate::constants::BASIS_POINT_MAX;
+use crate::errors::LBError;
+use crate::state::position::PositionV2;
+use crate::ModifyLiquidity;
+use anchor_lang::prelude::*;
+use num_traits::Zero;
+
+/// Build the bin liquidity reductions for the bins within [from_bin_id, to_bin_id] which the position holds liquidity. Bins out of the position range, or without liquidity share are skipped.
+pub fn get_bin_liquidity_reduction_in_range(
+    position: &PositionV2,
+    from_bin_id: i32,
+    to_bin_id: i32,
+    bps_to_remove: u16,
+) -> Result<Vec<BinLiquidityReduction>> {
+    require!(from_bin_id <= to_bin_id, LBError::InvalidInput);
+    require!(
+        bps_to_remove > 0 && bps_to_remove <= BASIS_POINT_MAX as u16,
+        LBError::InvalidBps
+    );
+
+    let min_bin_id = std::cmp::max(from_bin_id, position.lower_bin_id);
+    let max_bin_id = std::cmp::min(to_bin_id, position.upper_bin_id);
+
+    let mut bin_liquidity_reduction = vec![];
+    for bin_id in min_bin_id..=max_bin_id {
+        if position.get_liquidity_share_in_bin(bin_id)?.is_zero() {
+            continue;
+        }
+        bin_liquidity_reduction.push(BinLiquidityReduction {
+            bin_id,
+            bps_to_remove,
+        });
+    }
+
+    Ok(bin_liquidity_reduction)
+}
+
+pub fn handle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
+    from_bin_id: i32,
+    to_bin_id: i32,
+    bps_to_remove: u16,
+) -> Result<()> {
+    let bin_liquidity_reduction = {
+        let position = ctx.accounts.position.load()?;
+        get_bin_liquidity_reduction_in_range(&position, from_bin_id, to_bin_id, bps_to_remove)?
+    };
+
+    require!(!bin_liquidity_reduction.is_empty(), LBError::ZeroLiquidity);
+
+    super::remove_liquidity::handle(ctx, bin_liquidity_reduction)
+}
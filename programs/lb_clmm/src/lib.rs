@@ -13,8 +13,11 @@ pub mod state;
 pub mod utils;
 
 use instructions::admin::*;
+use instructions::claim_all::*;
 use instructions::claim_fee::*;
 use instructions::claim_reward::*;
+use instructions::close_bin_array::*;
+use instructions::close_lb_pair::*;
 use instructions::close_position::*;
 use instructions::create_position::*;
 use instructions::deposit::*;
@@ -23,11 +26,18 @@ use instructions::increase_oracle_length::*;
 use instructions::initialize_bin_array::*;
 use instructions::initialize_bin_array_bitmap_extension::*;
 use instructions::initialize_pool::*;
+use instructions::initialize_whitelisted_wallet::*;
 use instructions::migrate_bin_array::*;
+use instructions::migrate_liquidity::*;
 use instructions::migrate_position::*;
 use instructions::position_authorize::*;
+use instructions::position_trigger::*;
+use instructions::quote_swap::*;
+use instructions::range_order::*;
+use instructions::refresh_whitelisted_wallet::*;
 use instructions::set_pair_status_permissionless::*;
 use instructions::swap::*;
+use instructions::sync_pair_to_preset::*;
 use instructions::update_fees_and_rewards::*;
 use instructions::update_position_operator::*;
 use instructions::withdraw::*;
@@ -392,4 +402,170 @@ pub mod lb_clmm {
     ) -> Result<()> {
         instructions::set_pair_status_permissionless::handle(ctx, status)
     }
+
+    pub fn remove_liquidity_by_range<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
+        from_bin_id: i32,
+        to_bin_id: i32,
+        bps_to_remove: u16,
+    ) -> Result<()> {
+        instructions::withdraw::remove_liquidity_by_range::handle(
+            ctx,
+            from_bin_id,
+            to_bin_id,
+            bps_to_remove,
+        )
+    }
+
+    pub fn migrate_liquidity<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MigrateLiquidity<'info>>,
+        parameter: MigrateLiquidityParameter,
+    ) -> Result<()> {
+        instructions::migrate_liquidity::handle(ctx, parameter)
+    }
+
+    pub fn quote_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, QuoteSwap<'info>>,
+        amount_in: u64,
+        swap_for_y: bool,
+    ) -> Result<SwapQuote> {
+        instructions::quote_swap::handle(ctx, amount_in, swap_for_y)
+    }
+
+    pub fn swap_guarded<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        guard: SwapGuard,
+    ) -> Result<()> {
+        instructions::swap::handle_exact_in_guarded(ctx, amount_in, min_amount_out, guard)
+    }
+
+    pub fn swap_exact_out_guarded<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+        max_in_amount: u64,
+        out_amount: u64,
+        guard: SwapGuard,
+    ) -> Result<()> {
+        instructions::swap::handle_exact_out_guarded(ctx, max_in_amount, out_amount, guard)
+    }
+
+    pub fn swap_with_price_impact_guarded<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+        amount_in: u64,
+        active_id: Option<i32>,
+        max_price_impact_bps: u16,
+        guard: SwapGuard,
+    ) -> Result<()> {
+        instructions::swap::handle_exact_in_with_price_impact_guarded(
+            ctx,
+            amount_in,
+            active_id,
+            max_price_impact_bps,
+            guard,
+        )
+    }
+
+    pub fn set_whitelist_merkle_root(
+        ctx: Context<SetWhitelistMerkleRoot>,
+        whitelist_merkle_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::admin::set_whitelist_merkle_root::handle(ctx, whitelist_merkle_root)
+    }
+
+    pub fn initialize_whitelisted_wallet(
+        ctx: Context<InitializeWhitelistedWallet>,
+        parameter: WhitelistedWalletParameter,
+    ) -> Result<()> {
+        instructions::initialize_whitelisted_wallet::handle(ctx, parameter)
+    }
+
+    pub fn refresh_whitelisted_wallet(
+        ctx: Context<RefreshWhitelistedWallet>,
+        parameter: WhitelistedWalletParameter,
+    ) -> Result<()> {
+        instructions::refresh_whitelisted_wallet::handle(ctx, parameter)
+    }
+
+    pub fn set_position_trigger(
+        ctx: Context<SetPositionTrigger>,
+        trigger: Option<PositionTriggerParameter>,
+    ) -> Result<()> {
+        instructions::position_trigger::set_position_trigger::handle(ctx, trigger)
+    }
+
+    pub fn execute_position_trigger<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExecutePositionTrigger<'info>>,
+    ) -> Result<()> {
+        instructions::position_trigger::execute_position_trigger::handle(ctx)
+    }
+
+    pub fn close_range_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseRangeOrder<'info>>,
+    ) -> Result<()> {
+        instructions::range_order::close_range_order::handle(ctx)
+    }
+
+    pub fn update_preset_parameter(
+        ctx: Context<UpdatePresetParameter>,
+        ix: UpdatePresetParametersIx,
+    ) -> Result<()> {
+        instructions::admin::update_preset_parameter::handle(ctx, ix)
+    }
+
+    pub fn sync_pair_to_preset(ctx: Context<SyncPairToPreset>) -> Result<()> {
+        instructions::sync_pair_to_preset::handle(ctx)
+    }
+
+    pub fn decrease_oracle_length(
+        ctx: Context<DecreaseOracleLength>,
+        length_to_remove: u64,
+    ) -> Result<()> {
+        instructions::admin::decrease_oracle_length::handle(ctx, length_to_remove)
+    }
+
+    pub fn close_oracle(ctx: Context<CloseOracle>) -> Result<()> {
+        instructions::admin::close_oracle::handle(ctx)
+    }
+
+    pub fn close_bin_array<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseBinArray<'info>>,
+    ) -> Result<()> {
+        instructions::close_bin_array::handle(ctx)
+    }
+
+    pub fn close_lb_pair<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseLbPair<'info>>,
+    ) -> Result<()> {
+        instructions::close_lb_pair::handle(ctx)
+    }
+
+    pub fn claim_all<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ClaimAll<'info>>,
+    ) -> Result<()> {
+        instructions::claim_all::handle(ctx)
+    }
+
+    pub fn set_protocol_share(
+        ctx: Context<SetProtocolShare>,
+        ix: ProtocolShareParameter,
+    ) -> Result<()> {
+        instructions::admin::set_protocol_share::handle(ctx, ix)
+    }
+
+    pub fn initialize_fee_tier(ctx: Context<InitializeFeeTier>, discount_bps: u16) -> Result<()> {
+        instructions::admin::initialize_fee_tier::handle(ctx, discount_bps)
+    }
+
+    pub fn update_fee_tier(ctx: Context<UpdateFeeTier>, discount_bps: u16) -> Result<()> {
+        instructions::admin::update_fee_tier::handle(ctx, discount_bps)
+    }
+
+    pub fn close_fee_tier(ctx: Context<CloseFeeTier>) -> Result<()> {
+        instructions::admin::close_fee_tier::handle(ctx)
+    }
+
+    pub fn bootstrap_pair(ctx: Context<BootstrapPair>, params: BootstrapPairParams) -> Result<()> {
+        instructions::initialize_pool::bootstrap_pair::handle(ctx, params)
+    }
 }
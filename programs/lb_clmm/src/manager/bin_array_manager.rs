@@ -157,3 +157,43 @@ impl<'a, 'info> BinArrayManagerReadOnly<'a, 'info> {
         }
     }
 }
+
+/// Bins crossed by a swap. The swap loop run against the bin arrays of the swap, or against a simulation of them.
+pub trait SwapBinSource {
+    fn get_bin_mut(&mut self, bin_id: i32) -> Result<&mut Bin>;
+}
+
+impl<'a, 'info> SwapBinSource for BinArrayManager<'a, 'info> {
+    fn get_bin_mut(&mut self, bin_id: i32) -> Result<&mut Bin> {
+        BinArrayManager::get_bin_mut(self, bin_id)
+    }
+}
+
+/// A read only bin arrays container which copy the bin upon the first access. Used to simulate a swap without mutating the bin arrays.
+pub struct BinArrayManagerSimulation<'a, 'info> {
+    bin_array_manager: BinArrayManagerReadOnly<'a, 'info>,
+    bins: Vec<(i32, Bin)>,
+}
+
+impl<'a, 'info> BinArrayManagerSimulation<'a, 'info> {
+    pub fn new(bin_arrays: &'a [Ref<'info, BinArray>]) -> Result<Self> {
+        Ok(BinArrayManagerSimulation {
+            bin_array_manager: BinArrayManagerReadOnly::new(bin_arrays)?,
+            bins: vec![],
+        })
+    }
+}
+
+impl<'a, 'info> SwapBinSource for BinArrayManagerSimulation<'a, 'info> {
+    fn get_bin_mut(&mut self, bin_id: i32) -> Result<&mut Bin> {
+        let idx = match self.bins.iter().position(|(id, _)| *id == bin_id) {
+            Some(idx) => idx,
+            None => {
+                let bin = *self.bin_array_manager.get_bin(bin_id)?;
+                self.bins.push((bin_id, bin));
+                self.bins.len() - 1
+            }
+        };
+        Ok(&mut self.bins[idx].1)
+    }
+}
@@ -1,6 +1,8 @@
 use super::safe_math::SafeMath;
+use super::u128x128_math::Rounding;
 use super::u64x64_math::{pow, ONE, SCALE_OFFSET};
-use crate::constants::BASIS_POINT_MAX;
+use super::utils_math::safe_mul_div_cast;
+use crate::constants::{BASIS_POINT_MAX, MAX_BIN_ID, MIN_BIN_ID};
 use crate::errors::LBError;
 use anchor_lang::prelude::*;
 
@@ -17,3 +19,205 @@ pub fn get_price_from_id(active_id: i32, bin_step: u16) -> Result<u128> {
     let base = ONE.safe_add(bps)?;
     pow(base, active_id).ok_or_else(|| LBError::MathOverflow.into())
 }
+
+/// Calculate the bin id of the given price. The inverse of get_price_from_id. The price is in Q64.64
+/// Rounding::Down return the largest bin id which the bin price <= price. Rounding::Up return the smallest bin id which the bin price >= price.
+/// Bin price is rounded to the same Q64.64 value near the edges of the bin id range, ties resolve to the outermost bin id of the rounding direction.
+pub fn get_id_from_price(price: u128, bin_step: u16, rounding: Rounding) -> Result<i32> {
+    // Binary search for the first bin id past the given price, as the bin price is monotonically non-decreasing with the bin id
+    let mut low = MIN_BIN_ID;
+    let mut high = MAX_BIN_ID.safe_add(1)?;
+
+    while low < high {
+        let mid = low.safe_add(high.safe_sub(low)?.safe_div(2)?)?;
+        let is_past_price = match get_price_from_id(mid, bin_step) {
+            Ok(mid_price) => match rounding {
+                Rounding::Down => mid_price > price,
+                Rounding::Up => mid_price >= price,
+            },
+            // Out of Q64.64 range. Positive id overflow toward infinity, negative id toward zero.
+            Err(_) => !mid.is_negative(),
+        };
+
+        if is_past_price {
+            high = mid;
+        } else {
+            low = mid.safe_add(1)?;
+        }
+    }
+
+    let bin_id = match rounding {
+        Rounding::Up => low,
+        Rounding::Down => low.safe_sub(1)?,
+    };
+
+    // No bin price within the Q64.64 range satisfy the rounding
+    require!(
+        bin_id >= MIN_BIN_ID && bin_id <= MAX_BIN_ID && get_price_from_id(bin_id, bin_step).is_ok(),
+        LBError::BinIdOutOfBound
+    );
+
+    Ok(bin_id)
+}
+
+/// First bin priced beyond max_price_impact_bps from the price of active_id, in the swap direction. The swap with price impact stop before swapping in this bin.
+pub fn get_price_impact_limit_bin_id(
+    active_id: i32,
+    bin_step: u16,
+    swap_for_y: bool,
+    max_price_impact_bps: u16,
+) -> Result<i32> {
+    let active_price = get_price_from_id(active_id, bin_step)?;
+    let max_price_impact_bps = u128::from(max_price_impact_bps);
+
+    if swap_for_y {
+        let min_price: u128 = safe_mul_div_cast(
+            active_price,
+            (BASIS_POINT_MAX as u128).saturating_sub(max_price_impact_bps),
+            BASIS_POINT_MAX as u128,
+            Rounding::Up,
+        )?;
+        let min_bin_id = get_id_from_price(min_price, bin_step, Rounding::Up).unwrap_or(MIN_BIN_ID);
+        Ok(min_bin_id.safe_sub(1)?)
+    } else {
+        let max_price: u128 = safe_mul_div_cast(
+            active_price,
+            (BASIS_POINT_MAX as u128).safe_add(max_price_impact_bps)?,
+            BASIS_POINT_MAX as u128,
+            Rounding::Down,
+        )?;
+        let max_bin_id =
+            get_id_from_price(max_price, bin_step, Rounding::Down).unwrap_or(MAX_BIN_ID);
+        Ok(max_bin_id.safe_add(1)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIN_STEPS: [u16; 6] = [1, 5, 10, 25, 100, 400];
+
+    #[test]
+    fn test_get_id_from_price_round_trip() {
+        for bin_step in BIN_STEPS {
+            for bin_id in (-1000..=1000).step_by(7) {
+                let price = get_price_from_id(bin_id, bin_step).unwrap();
+                assert_eq!(
+                    get_id_from_price(price, bin_step, Rounding::Down).unwrap(),
+                    bin_id
+                );
+                assert_eq!(
+                    get_id_from_price(price, bin_step, Rounding::Up).unwrap(),
+                    bin_id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_id_from_price_rounding() {
+        for bin_step in BIN_STEPS {
+            for bin_id in [-500, -1, 0, 1, 500] {
+                let price = get_price_from_id(bin_id, bin_step).unwrap();
+                let next_price = get_price_from_id(bin_id + 1, bin_step).unwrap();
+
+                for price_between in [price + 1, (price + next_price) / 2, next_price - 1] {
+                    assert_eq!(
+                        get_id_from_price(price_between, bin_step, Rounding::Down).unwrap(),
+                        bin_id
+                    );
+                    assert_eq!(
+                        get_id_from_price(price_between, bin_step, Rounding::Up).unwrap(),
+                        bin_id + 1
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_id_from_price_min_max_bin_id() {
+        // Bin step 1 cover the whole Q64.64 range with MIN_BIN_ID, and MAX_BIN_ID
+        let min_price = get_price_from_id(MIN_BIN_ID, 1).unwrap();
+        let max_price = get_price_from_id(MAX_BIN_ID, 1).unwrap();
+
+        assert_eq!(
+            get_id_from_price(max_price, 1, Rounding::Down).unwrap(),
+            MAX_BIN_ID
+        );
+        assert_eq!(
+            get_id_from_price(u128::MAX, 1, Rounding::Down).unwrap(),
+            MAX_BIN_ID
+        );
+        assert_eq!(
+            get_id_from_price(min_price, 1, Rounding::Up).unwrap(),
+            MIN_BIN_ID
+        );
+        assert_eq!(get_id_from_price(0, 1, Rounding::Up).unwrap(), MIN_BIN_ID);
+
+        // Bin price is rounded to the same value near the edges
+        let bin_id = get_id_from_price(max_price, 1, Rounding::Up).unwrap();
+        assert!(bin_id <= MAX_BIN_ID);
+        assert_eq!(get_price_from_id(bin_id, 1).unwrap(), max_price);
+        assert!(get_price_from_id(bin_id - 1, 1).unwrap() < max_price);
+
+        let bin_id = get_id_from_price(min_price, 1, Rounding::Down).unwrap();
+        assert!(bin_id >= MIN_BIN_ID);
+        assert_eq!(get_price_from_id(bin_id, 1).unwrap(), min_price);
+        assert!(get_price_from_id(bin_id + 1, 1).unwrap() > min_price);
+
+        // No bin price below the price of MIN_BIN_ID
+        assert!(get_id_from_price(min_price - 1, 1, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn test_get_id_from_price_out_of_q64x64_range() {
+        for bin_step in BIN_STEPS.into_iter().skip(1) {
+            // Largest, and smallest bin id with price within Q64.64 range
+            let max_bin_id = get_id_from_price(u128::MAX, bin_step, Rounding::Down).unwrap();
+            let min_bin_id = get_id_from_price(0, bin_step, Rounding::Up).unwrap();
+
+            assert!(max_bin_id < MAX_BIN_ID && min_bin_id > MIN_BIN_ID);
+            assert!(get_price_from_id(max_bin_id, bin_step).is_ok());
+            assert!(get_price_from_id(max_bin_id + 1, bin_step).is_err());
+            assert!(get_price_from_id(min_bin_id, bin_step).is_ok());
+            assert!(get_price_from_id(min_bin_id - 1, bin_step).is_err());
+
+            assert!(get_id_from_price(0, bin_step, Rounding::Down).is_err());
+        }
+    }
+
+    #[test]
+    fn test_get_price_impact_limit_bin_id() {
+        for bin_step in BIN_STEPS {
+            // No price impact allowed. Only the active bin is swapped.
+            assert_eq!(
+                get_price_impact_limit_bin_id(10, bin_step, true, 0).unwrap(),
+                9
+            );
+            assert_eq!(
+                get_price_impact_limit_bin_id(10, bin_step, false, 0).unwrap(),
+                11
+            );
+
+            for max_price_impact_bps in [1, 50, 500, 5_000] {
+                let active_price = get_price_from_id(10, bin_step).unwrap();
+
+                let limit_bin_id =
+                    get_price_impact_limit_bin_id(10, bin_step, true, max_price_impact_bps)
+                        .unwrap();
+                let min_price = active_price * u128::from(10_000 - max_price_impact_bps) / 10_000;
+                assert!(get_price_from_id(limit_bin_id, bin_step).unwrap() < min_price);
+                assert!(get_price_from_id(limit_bin_id + 1, bin_step).unwrap() >= min_price);
+
+                let limit_bin_id =
+                    get_price_impact_limit_bin_id(10, bin_step, false, max_price_impact_bps)
+                        .unwrap();
+                let max_price = active_price * u128::from(10_000 + max_price_impact_bps) / 10_000;
+                assert!(get_price_from_id(limit_bin_id, bin_step).unwrap() > max_price);
+                assert!(get_price_from_id(limit_bin_id - 1, bin_step).unwrap() <= max_price);
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@ use crate::pair_action_access::{
 };
 use crate::state::lb_pair::LbPair;
 use crate::state::lb_pair::PairType;
+use crate::state::whitelisted_wallet::WhitelistedWallet;
 use anchor_lang::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use solana_program::pubkey::Pubkey;
@@ -18,15 +19,26 @@ pub enum ActivationType {
 }
 
 pub trait LbPairTypeActionAccess {
-    fn validate_add_liquidity_access(&self) -> bool;
+    fn validate_add_liquidity_access(
+        &self,
+        sender: Pubkey,
+        whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool;
     // in customizable permissionless pool, we doesn't allow user to deposit quote token in active bin before activation_point (because they can't withdraw liquidity before activation_point or do internal swap)
     fn validate_deposit_quote_token_in_active_bin(&self) -> bool;
     fn validate_remove_liquidity_access(&self, is_ask_side: bool) -> Result<bool>;
-    fn validate_swap_access(&self, sender: Pubkey) -> bool;
+    fn validate_swap_access(
+        &self,
+        sender: Pubkey,
+        whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool;
+    // whitelisted wallets are capped during the pre-activation window only
+    fn is_in_pre_activation_window(&self) -> bool;
     fn get_current_point(&self) -> u64;
     fn validate_update_new_activation_point(&self, new_activation_point: u64) -> Result<()>;
     fn validate_set_pre_activation_duration(&self, new_pre_activation_duration: u64) -> Result<()>;
     fn validate_set_pre_activation_swap_address(&self) -> Result<()>;
+    fn validate_set_whitelist_merkle_root(&self) -> Result<()>;
     fn validate_initialize_position_by_operator(&self) -> bool;
     fn validate_initialize_position(&self) -> bool;
     fn validate_initialize_bin_array(&self) -> bool;
@@ -1,6 +1,7 @@
 use crate::pair_action_access::ActivationType;
 use crate::pair_action_access::LbPairTypeActionAccess;
 use crate::state::lb_pair::{LbPair, PairStatus};
+use crate::state::whitelisted_wallet::WhitelistedWallet;
 use crate::{
     constants::{SLOT_BUFFER, TIME_BUFFER},
     errors::LBError,
@@ -34,7 +35,11 @@ impl CustomizablePermissionlessLbPairActionAccess {
 }
 
 impl LbPairTypeActionAccess for CustomizablePermissionlessLbPairActionAccess {
-    fn validate_add_liquidity_access(&self) -> bool {
+    fn validate_add_liquidity_access(
+        &self,
+        _sender: Pubkey,
+        _whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool {
         self.is_enabled
     }
 
@@ -51,7 +56,11 @@ impl LbPairTypeActionAccess for CustomizablePermissionlessLbPairActionAccess {
         }
     }
 
-    fn validate_swap_access(&self, sender: Pubkey) -> bool {
+    fn validate_swap_access(
+        &self,
+        sender: Pubkey,
+        _whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool {
         let activation_point = if self.pre_activation_swap_address.eq(&sender) {
             self.activation_point
                 .saturating_sub(self.pre_activation_duration)
@@ -61,6 +70,9 @@ impl LbPairTypeActionAccess for CustomizablePermissionlessLbPairActionAccess {
 
         self.is_enabled && self.current_point >= activation_point
     }
+    fn is_in_pre_activation_window(&self) -> bool {
+        false
+    }
     fn get_current_point(&self) -> u64 {
         self.current_point
     }
@@ -76,6 +88,9 @@ impl LbPairTypeActionAccess for CustomizablePermissionlessLbPairActionAccess {
     fn validate_set_pre_activation_swap_address(&self) -> Result<()> {
         Err(LBError::UnauthorizedAccess.into())
     }
+    fn validate_set_whitelist_merkle_root(&self) -> Result<()> {
+        Err(LBError::UnauthorizedAccess.into())
+    }
 
     fn validate_initialize_position_by_operator(&self) -> bool {
         self.current_point < self.activation_point
@@ -5,6 +5,7 @@ use crate::pair_action_access::validate_activation_point;
 use crate::pair_action_access::ActivationType;
 use crate::pair_action_access::LbPairTypeActionAccess;
 use crate::state::lb_pair::{LbPair, PairStatus};
+use crate::state::whitelisted_wallet::WhitelistedWallet;
 use crate::{
     constants::{SLOT_BUFFER, TIME_BUFFER},
     errors::LBError,
@@ -14,6 +15,7 @@ use solana_program::pubkey::Pubkey;
 pub struct PermissionLbPairActionAccess {
     is_enabled: bool,
     pre_activation_swap_address: Pubkey,
+    whitelist_merkle_root: [u8; 32],
     activation_point: u64,
     current_point: u64,
     pre_activation_duration: u64,
@@ -44,6 +46,11 @@ impl PermissionLbPairActionAccess {
         Ok(Self {
             is_enabled: lb_pair.status == Into::<u8>::into(PairStatus::Enabled),
             pre_activation_swap_address: lb_pair.pre_activation_swap_address,
+            whitelist_merkle_root: if lb_pair.is_whitelist_enabled() {
+                lb_pair.whitelist_merkle_root
+            } else {
+                [0u8; 32]
+            },
             activation_point: lb_pair.activation_point,
             current_point,
             pre_activation_duration: lb_pair.pre_activation_duration,
@@ -54,8 +61,32 @@ impl PermissionLbPairActionAccess {
     }
 }
 
+impl PermissionLbPairActionAccess {
+    fn get_pre_activation_point(&self) -> u64 {
+        self.activation_point
+            .saturating_sub(self.pre_activation_duration)
+    }
+
+    fn is_whitelisted(
+        &self,
+        sender: Pubkey,
+        whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool {
+        self.whitelist_merkle_root != [0u8; 32]
+            && whitelisted_wallet.map_or(false, |w| w.is_valid(sender, self.whitelist_merkle_root))
+    }
+}
+
 impl LbPairTypeActionAccess for PermissionLbPairActionAccess {
-    fn validate_add_liquidity_access(&self) -> bool {
+    fn validate_add_liquidity_access(
+        &self,
+        sender: Pubkey,
+        whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool {
+        // Only whitelisted wallets can deposit during the pre-activation window when the whitelist is enabled
+        if self.whitelist_merkle_root != [0u8; 32] && self.is_in_pre_activation_window() {
+            return self.is_enabled && self.is_whitelisted(sender, whitelisted_wallet);
+        }
         self.is_enabled
     }
 
@@ -67,16 +98,25 @@ impl LbPairTypeActionAccess for PermissionLbPairActionAccess {
         Ok(true)
     }
 
-    fn validate_swap_access(&self, sender: Pubkey) -> bool {
-        let activation_point = if self.pre_activation_swap_address.eq(&sender) {
-            self.activation_point
-                .saturating_sub(self.pre_activation_duration)
+    fn validate_swap_access(
+        &self,
+        sender: Pubkey,
+        whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool {
+        let activation_point = if self.pre_activation_swap_address.eq(&sender)
+            || self.is_whitelisted(sender, whitelisted_wallet)
+        {
+            self.get_pre_activation_point()
         } else {
             self.activation_point
         };
 
         self.is_enabled && self.current_point >= activation_point
     }
+    fn is_in_pre_activation_window(&self) -> bool {
+        self.current_point >= self.get_pre_activation_point()
+            && self.current_point < self.activation_point
+    }
     fn get_current_point(&self) -> u64 {
         self.current_point
     }
@@ -151,6 +191,11 @@ impl LbPairTypeActionAccess for PermissionLbPairActionAccess {
         Ok(())
     }
 
+    fn validate_set_whitelist_merkle_root(&self) -> Result<()> {
+        // Same as pre-activation swap address, the whitelist is frozen once the pool enter pre-activation phase
+        self.validate_set_pre_activation_swap_address()
+    }
+
     fn validate_initialize_position_by_operator(&self) -> bool {
         self.current_point < self.activation_point
     }
@@ -161,3 +206,84 @@ impl LbPairTypeActionAccess for PermissionLbPairActionAccess {
         self.is_enabled
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(whitelist_merkle_root: [u8; 32], current_point: u64) -> PermissionLbPairActionAccess {
+        PermissionLbPairActionAccess {
+            is_enabled: true,
+            pre_activation_swap_address: Pubkey::default(),
+            whitelist_merkle_root,
+            activation_point: 1_000,
+            current_point,
+            pre_activation_duration: 100,
+            time_buffer: TIME_BUFFER,
+            deposit_close_idle_duration: FIVE_MINUTES_TIME_BUFFER,
+            last_join_buffer: FIVE_MINUTES_TIME_BUFFER,
+        }
+    }
+
+    fn whitelisted_wallet(wallet: Pubkey, merkle_root: [u8; 32]) -> WhitelistedWallet {
+        let mut whitelisted_wallet: WhitelistedWallet = bytemuck::Zeroable::zeroed();
+        whitelisted_wallet.init(
+            Pubkey::new_unique(),
+            wallet,
+            merkle_root,
+            &crate::state::whitelisted_wallet::tests::parameter(),
+        );
+        whitelisted_wallet
+    }
+
+    #[test]
+    fn test_whitelisted_wallet_swap_in_pre_activation_window() {
+        let wallet = Pubkey::new_unique();
+        let whitelisted_wallet = whitelisted_wallet(wallet, [1u8; 32]);
+        let access = access([1u8; 32], 950);
+
+        assert!(access.is_in_pre_activation_window());
+        assert!(access.validate_swap_access(wallet, Some(&whitelisted_wallet)));
+        assert!(!access.validate_swap_access(wallet, None));
+        assert!(!access.validate_swap_access(Pubkey::new_unique(), Some(&whitelisted_wallet)));
+    }
+
+    #[test]
+    fn test_whitelisted_wallet_invalidated_by_merkle_root() {
+        let wallet = Pubkey::new_unique();
+        let whitelisted_wallet = whitelisted_wallet(wallet, [1u8; 32]);
+
+        let access = access([2u8; 32], 950);
+        assert!(!access.validate_swap_access(wallet, Some(&whitelisted_wallet)));
+        assert!(!access.validate_add_liquidity_access(wallet, Some(&whitelisted_wallet)));
+    }
+
+    #[test]
+    fn test_whitelist_gated_deposit() {
+        let wallet = Pubkey::new_unique();
+        let whitelisted_wallet = whitelisted_wallet(wallet, [1u8; 32]);
+
+        // Only whitelisted wallets deposit during the pre-activation window
+        let access_in_window = access([1u8; 32], 950);
+        assert!(access_in_window.validate_add_liquidity_access(wallet, Some(&whitelisted_wallet)));
+        assert!(!access_in_window.validate_add_liquidity_access(wallet, None));
+
+        // Open to everyone outside of the window, or when the whitelist is disabled
+        assert!(access([1u8; 32], 800).validate_add_liquidity_access(wallet, None));
+        assert!(access([0u8; 32], 950).validate_add_liquidity_access(wallet, None));
+    }
+
+    #[test]
+    fn test_stale_merkle_root_ignored_when_whitelist_disabled() {
+        let mut lb_pair = LbPair::default();
+        // Leftover bytes from the region the merkle root used to occupy
+        lb_pair.whitelist_merkle_root = [1u8; 32];
+        assert!(!lb_pair.is_whitelist_enabled());
+
+        lb_pair.set_whitelist_merkle_root([1u8; 32]);
+        assert!(lb_pair.is_whitelist_enabled());
+
+        lb_pair.set_whitelist_merkle_root([0u8; 32]);
+        assert!(!lb_pair.is_whitelist_enabled());
+    }
+}
@@ -2,6 +2,7 @@ use crate::errors::LBError;
 use crate::pair_action_access::ActivationType;
 use crate::pair_action_access::LbPairTypeActionAccess;
 use crate::state::lb_pair::{LbPair, PairStatus};
+use crate::state::whitelisted_wallet::WhitelistedWallet;
 use anchor_lang::prelude::*;
 pub struct PermissionlessLbPairActionAccess {
     is_enabled: bool,
@@ -24,7 +25,11 @@ impl PermissionlessLbPairActionAccess {
 }
 
 impl LbPairTypeActionAccess for PermissionlessLbPairActionAccess {
-    fn validate_add_liquidity_access(&self) -> bool {
+    fn validate_add_liquidity_access(
+        &self,
+        _sender: Pubkey,
+        _whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool {
         self.is_enabled
     }
 
@@ -36,10 +41,17 @@ impl LbPairTypeActionAccess for PermissionlessLbPairActionAccess {
         Ok(true)
     }
 
-    fn validate_swap_access(&self, _sender: Pubkey) -> bool {
+    fn validate_swap_access(
+        &self,
+        _sender: Pubkey,
+        _whitelisted_wallet: Option<&WhitelistedWallet>,
+    ) -> bool {
         self.is_enabled
     }
 
+    fn is_in_pre_activation_window(&self) -> bool {
+        false
+    }
     fn get_current_point(&self) -> u64 {
         self.current_point
     }
@@ -55,6 +67,9 @@ impl LbPairTypeActionAccess for PermissionlessLbPairActionAccess {
     fn validate_set_pre_activation_swap_address(&self) -> Result<()> {
         Err(LBError::UnauthorizedAccess.into())
     }
+    fn validate_set_whitelist_merkle_root(&self) -> Result<()> {
+        Err(LBError::UnauthorizedAccess.into())
+    }
 
     fn validate_initialize_position_by_operator(&self) -> bool {
         false
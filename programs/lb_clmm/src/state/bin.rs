@@ -1,6 +1,6 @@
 use std::cell::RefMut;
 
-use super::lb_pair::LbPair;
+use super::lb_pair::{LbPair, SwapFeeModifier};
 use crate::{
     constants::{BASIS_POINT_MAX, MAX_BIN_ID, MAX_BIN_PER_ARRAY, MIN_BIN_ID, NUM_REWARDS},
     errors::*,
@@ -153,6 +153,7 @@ impl Bin {
         swap_for_y: bool,
         lb_pair: &LbPair,
         host_fee_bps: Option<u16>,
+        fee_modifier: &SwapFeeModifier,
     ) -> Result<SwapResult> {
         // Get maximum out token amount can be swapped out from the bin.
         let max_amount_out = self.get_max_amount_out(swap_for_y);
@@ -163,7 +164,7 @@ impl Bin {
         // When the amount_in > max_amount_in, it will swap finish all the current bin token X/Y based on the swap direction.
         // However, max_amount_in is amount that required to swap finish the current bin without fee
         // Therefore, we need find max_amount_in_include_fees, where max_amount_in_include_fees - fee = max_amount_in
-        let max_fee = lb_pair.compute_fee(max_amount_in)?;
+        let max_fee = lb_pair.compute_fee(max_amount_in, fee_modifier)?;
         max_amount_in = max_amount_in.safe_add(max_fee)?;
 
         // If the in token amount > maximum token amount needed to swap out all of the opposite token from the bin.
@@ -176,7 +177,7 @@ impl Bin {
             )
         } else {
             // TODO: User possible to bypass fee by swapping small amount ? User do a "normal" swap by just bundling all small swap that bypass fee ?
-            let fee = lb_pair.compute_fee_from_amount(amount_in)?;
+            let fee = lb_pair.compute_fee_from_amount(amount_in, fee_modifier)?;
             let amount_in_after_fee = amount_in.safe_sub(fee)?;
             let amount_out = Bin::get_amount_out(amount_in_after_fee, price, swap_for_y)?;
             (
@@ -217,6 +218,7 @@ impl Bin {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn swap_exact_out(
         &mut self,
         amount_in: u64,
@@ -224,19 +226,27 @@ impl Bin {
         swap_for_y: bool,
         lb_pair: &LbPair,
         host_fee_bps: Option<u16>,
+        fee_modifier: &SwapFeeModifier,
         exact_out_amount: u64,
     ) -> Result<SwapResult> {
         // Get maximum out token amount can be swapped out from the bin.
         let max_amount_out = self.get_max_amount_out(swap_for_y);
         if exact_out_amount >= max_amount_out {
-            let mut swap_result = self.swap(amount_in, price, swap_for_y, lb_pair, host_fee_bps)?;
+            let mut swap_result = self.swap(
+                amount_in,
+                price,
+                swap_for_y,
+                lb_pair,
+                host_fee_bps,
+                fee_modifier,
+            )?;
             if exact_out_amount == max_amount_out {
                 swap_result.is_exact_out_amount = true;
             }
             return Ok(swap_result);
         } else {
             let exact_amount_in = Bin::get_amount_in(exact_out_amount, price, swap_for_y)?;
-            let fee = lb_pair.compute_fee(exact_amount_in)?;
+            let fee = lb_pair.compute_fee(exact_amount_in, fee_modifier)?;
             let amount_in_with_fees = exact_amount_in.safe_add(fee)?;
             let mut swap_result = self.swap(
                 amount_in_with_fees,
@@ -244,6 +254,7 @@ impl Bin {
                 swap_for_y,
                 lb_pair,
                 host_fee_bps,
+                fee_modifier,
             )?;
             swap_result.is_exact_out_amount = true;
             Ok(swap_result)
@@ -107,6 +107,13 @@ impl BinArrayBitmapExtension {
         );
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.positive_bin_array_bitmap
+            .iter()
+            .chain(self.negative_bin_array_bitmap.iter())
+            .all(|bitmap| bitmap.iter().all(|bits| *bits == 0))
+    }
+
     pub fn iter_bitmap(&self, start_index: i32, end_index: i32) -> Result<Option<i32>> {
         let offset: usize = Self::get_bitmap_offset(start_index)?;
         let bin_array_offset = Self::bin_array_offset_in_bitmap(start_index)?;
@@ -0,0 +1,73 @@
+use crate::constants::{MAX_FEE_DISCOUNT_BPS, NO_FEE_DISCOUNT_MULTIPLIER_BPS};
+use crate::errors::LBError;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Discounted swap fee granted to a wallet on a pair. Managed by admin for designated market makers.
+pub struct FeeTier {
+    /// The pair the discount is applied on
+    pub lb_pair: Pubkey,
+    /// Wallet entitled to the discount. Must be the user of the swap.
+    pub wallet: Pubkey,
+    /// Discount of the swap fee in bps
+    pub discount_bps: u16,
+    /// Padding for bytemuck safe alignment
+    pub _padding: [u8; 6],
+    /// Reserved space for future use
+    pub _reserved: [u8; 32],
+}
+
+impl FeeTier {
+    pub fn init(&mut self, lb_pair: Pubkey, wallet: Pubkey, discount_bps: u16) -> Result<()> {
+        self.lb_pair = lb_pair;
+        self.wallet = wallet;
+        self.update(discount_bps)
+    }
+
+    pub fn update(&mut self, discount_bps: u16) -> Result<()> {
+        require!(
+            discount_bps > 0 && discount_bps <= MAX_FEE_DISCOUNT_BPS,
+            LBError::InvalidFeeDiscount
+        );
+        self.discount_bps = discount_bps;
+        Ok(())
+    }
+
+    /// Multiplier in bps applied on the swap fee rate
+    pub fn get_fee_multiplier_bps(&self) -> u16 {
+        NO_FEE_DISCOUNT_MULTIPLIER_BPS.saturating_sub(self.discount_bps)
+    }
+}
+
+/// Fee tier of the swap user is passed as the next remaining account, after the whitelisted wallet if any. The remaining accounts are advanced when it is present.
+pub fn load_fee_tier<'c, 'info>(
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    lb_pair: Pubkey,
+    user: Pubkey,
+) -> Result<Option<AccountLoader<'info, FeeTier>>> {
+    let Some(account_info) = remaining_accounts.first() else {
+        return Ok(None);
+    };
+
+    let is_fee_tier = account_info.owner.eq(&crate::ID)
+        && account_info
+            .try_borrow_data()?
+            .starts_with(&FeeTier::discriminator());
+
+    if !is_fee_tier {
+        return Ok(None);
+    }
+
+    let fee_tier = AccountLoader::<FeeTier>::try_from(account_info)?;
+    {
+        let state = fee_tier.load()?;
+        require!(state.lb_pair == lb_pair, LBError::InvalidInput);
+        require!(state.wallet == user, LBError::UnauthorizedAccess);
+    }
+
+    *remaining_accounts = &remaining_accounts[1..];
+
+    Ok(Some(fee_tier))
+}
@@ -1,9 +1,10 @@
-use std::cmp::min;
+use std::cmp::{max, min};
 
 use crate::assert_eq_admin;
 use crate::constants::{
-    BASIS_POINT_MAX, BIN_ARRAY_BITMAP_SIZE, FEE_PRECISION, MAX_BIN_ID, MAX_FEE_RATE,
-    MAX_FEE_UPDATE_WINDOW, MIN_BIN_ID,
+    BASIS_POINT_MAX, BIN_ARRAY_BITMAP_SIZE, FEE_PRECISION, ILM_PROTOCOL_SHARE, MAX_BIN_ID,
+    MAX_FEE_RATE, MAX_FEE_UPDATE_WINDOW, MAX_PROTOCOL_SHARE, MAX_VOLATILITY_SURCHARGE_BPS,
+    MIN_BIN_ID, NO_FEE_DISCOUNT_MULTIPLIER_BPS,
 };
 use crate::instructions::admin::update_fee_parameters::FeeParameter;
 use crate::math::u128x128_math::Rounding;
@@ -12,6 +13,8 @@ use crate::math::utils_math::{one, safe_mul_div_cast, safe_mul_shr_cast, safe_sh
 use crate::state::bin::BinArray;
 use crate::state::bin_array_bitmap_extension::BinArrayBitmapExtension;
 use crate::state::parameters::{StaticParameters, VariableParameters};
+use crate::state::preset_parameters::PresetParameter;
+use crate::utils::seeds::ILM_BASE_KEY;
 use crate::{errors::LBError, math::safe_math::SafeMath};
 use anchor_lang::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -20,6 +23,24 @@ use std::ops::BitXor;
 use std::ops::Shl;
 use std::ops::Shr;
 
+/// Fee adjustment of a single swap. It is computed per swap, and never persisted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SwapFeeModifier {
+    /// Volatility surcharge fee in bps. Non zero only when the swap reverses earlier swaps within the same slot.
+    pub surcharge_bps: u16,
+    /// Multiplier in bps applied on the swap fee rate of the user. NO_FEE_DISCOUNT_MULTIPLIER_BPS for no discount.
+    pub fee_multiplier_bps: u16,
+}
+
+impl Default for SwapFeeModifier {
+    fn default() -> Self {
+        Self {
+            surcharge_bps: 0,
+            fee_multiplier_bps: NO_FEE_DISCOUNT_MULTIPLIER_BPS,
+        }
+    }
+}
+
 /// Type of the Pair. 0 = Permissionless, 1 = Permission, 2 = CustomizablePermissionless
 #[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
@@ -117,8 +138,8 @@ pub struct LbPair {
     pub bin_array_bitmap: [u64; 16], // store default bin id from -512 to 511 (bin id from -35840 to 35840, price from 2.7e-16 to 3.6e15)
     /// Last time the pool fee parameter was updated
     pub last_updated_at: i64,
-    /// _padding_2, previous whitelisted_wallet, BE CAREFUL FOR TOMBSTONE WHEN REUSE !!
-    pub _padding_2: [u8; 32],
+    /// Merkle root of the wallets allowed to swap and deposit during the pre-activation window. Only applicable for permission pair. Reused from previous whitelisted_wallet, old pairs may contain tombstone value. Only valid when whitelist_enabled is set.
+    pub whitelist_merkle_root: [u8; 32],
     /// Address allowed to swap when the current point is greater than or equal to the pre-activation point. The pre-activation point is calculated as `activation_point - pre_activation_duration`.
     pub pre_activation_swap_address: Pubkey,
     /// Base keypair. Only required for permission pair
@@ -127,14 +148,24 @@ pub struct LbPair {
     pub activation_point: u64,
     /// Duration before activation point. Used to calculate pre-activation point for pre_activation_swap_address
     pub pre_activation_duration: u64,
-    /// _padding 3 is reclaimed free space from swap_cap_deactivate_point and swap_cap_amount before, BE CAREFUL FOR TOMBSTONE WHEN REUSE !!
-    pub _padding_3: [u8; 8],
+    /// Point (slot or timestamp) where the protocol share steps down to parameters.target_protocol_share. Reused from _padding_3, only valid when parameters.protocol_share_step_down_enabled is set.
+    pub protocol_share_step_down_point: u64,
     /// _padding_4, previous lock_duration, BE CAREFUL FOR TOMBSTONE WHEN REUSE !!
     pub _padding_4: u64,
     /// Pool creator
     pub creator: Pubkey,
-    /// Reserved space for future use
-    pub _reserved: [u8; 24],
+    /// Swap volume of the direction tracked in last_swap_slot. Reversing swaps are charged with volatility surcharge until the volume is unwound.
+    pub last_slot_swap_volume: u64,
+    /// Version of the preset parameter the pair last synced to
+    pub preset_parameter_version: u64,
+    /// Number of open positions. Only accurate when account_tracking_enabled is set.
+    pub position_count: u32,
+    /// Number of initialized bin arrays. Only accurate when account_tracking_enabled is set.
+    pub bin_array_count: u16,
+    /// Whether whitelist_merkle_root is set by set_whitelist_merkle_root. 0 = No, 1 = Yes
+    pub whitelist_enabled: u8,
+    /// Whether position_count, and bin_array_count are tracked since the pair creation. Pairs created before the counters were introduced are not. 0 = No, 1 = Yes
+    pub account_tracking_enabled: u8,
 }
 
 impl Default for LbPair {
@@ -168,9 +199,14 @@ impl Default for LbPair {
             pre_activation_duration: 0,
             creator_pool_on_off_control: 0u8,
             _padding_1: [0u8; 32],
-            _padding_2: [0u8; 32],
-            _padding_3: [0u8; 8],
-            _reserved: [0u8; 24],
+            whitelist_merkle_root: [0u8; 32],
+            protocol_share_step_down_point: 0,
+            last_slot_swap_volume: 0,
+            preset_parameter_version: 0,
+            position_count: 0,
+            bin_array_count: 0,
+            whitelist_enabled: 0,
+            account_tracking_enabled: 0,
         }
     }
 }
@@ -345,6 +381,7 @@ impl LbPair {
 
         self.base_factor_seed = self.parameters.base_factor.to_le_bytes();
         self.activation_type = activation_type;
+        self.account_tracking_enabled = 1;
 
         Ok(())
     }
@@ -384,11 +421,128 @@ impl LbPair {
         }
 
         self.parameters.update(parameter)?;
+        // Protocol share is set explicitly, scheduled step down no longer applies
+        self.clear_protocol_share_step_down();
         self.last_updated_at = current_timestamp;
 
         Ok(())
     }
 
+    /// Apply the static parameters of the preset parameter. Pair specific protocol share, step down schedule, and volatility surcharge are retained.
+    pub fn sync_to_preset_parameter(&mut self, preset_parameter: &PresetParameter, version: u64) {
+        let StaticParameters {
+            protocol_share,
+            volatility_surcharge_bps,
+            target_protocol_share,
+            protocol_share_step_down_enabled,
+            ..
+        } = self.parameters;
+
+        self.parameters = preset_parameter.to_static_parameters();
+        self.parameters.protocol_share = protocol_share;
+        self.parameters.volatility_surcharge_bps = volatility_surcharge_bps;
+        self.parameters.target_protocol_share = target_protocol_share;
+        self.parameters.protocol_share_step_down_enabled = protocol_share_step_down_enabled;
+        self.preset_parameter_version = version;
+    }
+
+    /// Signer seeds of the pair based on the pair type
+    pub fn signer_seeds(&self) -> Result<Vec<Vec<u8>>> {
+        let min_mint = min(self.token_x_mint, self.token_y_mint);
+        let max_mint = max(self.token_x_mint, self.token_y_mint);
+
+        let pair_type: PairType = self
+            .pair_type
+            .try_into()
+            .map_err(|_| LBError::TypeCastFailed)?;
+
+        let mut seeds = match pair_type {
+            PairType::Permission => vec![
+                self.base_key.to_bytes().to_vec(),
+                min_mint.to_bytes().to_vec(),
+                max_mint.to_bytes().to_vec(),
+                self.bin_step_seed.to_vec(),
+            ],
+            PairType::CustomizablePermissionless => vec![
+                ILM_BASE_KEY.to_bytes().to_vec(),
+                min_mint.to_bytes().to_vec(),
+                max_mint.to_bytes().to_vec(),
+            ],
+            PairType::Permissionless => {
+                let mut seeds = vec![
+                    min_mint.to_bytes().to_vec(),
+                    max_mint.to_bytes().to_vec(),
+                    self.bin_step_seed.to_vec(),
+                ];
+                if self.require_base_factor_seed != 0 {
+                    seeds.push(self.base_factor_seed.to_vec());
+                }
+                seeds
+            }
+        };
+        seeds.push(self.bump_seed.to_vec());
+
+        Ok(seeds)
+    }
+
+    pub fn increase_position_count(&mut self) -> Result<()> {
+        self.position_count = self.position_count.safe_add(1)?;
+        Ok(())
+    }
+
+    /// Saturating as positions created before the counter was introduced are not tracked
+    pub fn decrease_position_count(&mut self) {
+        self.position_count = self.position_count.saturating_sub(1);
+    }
+
+    pub fn increase_bin_array_count(&mut self) -> Result<()> {
+        self.bin_array_count = self.bin_array_count.safe_add(1)?;
+        Ok(())
+    }
+
+    /// Saturating as bin arrays created before the counter was introduced are not tracked
+    pub fn decrease_bin_array_count(&mut self, count: u16) {
+        self.bin_array_count = self.bin_array_count.saturating_sub(count);
+    }
+
+    /// Pair without any position, liquidity, protocol fee and farming reward. Positions of pairs created before the counters were introduced are unknown, their reserves must be empty instead.
+    pub fn validate_empty(&self) -> Result<()> {
+        if self.is_account_tracking_enabled() {
+            require!(self.position_count == 0, LBError::NonEmptyPosition);
+        }
+        require!(
+            self.protocol_fee.amount_x == 0 && self.protocol_fee.amount_y == 0,
+            LBError::PairNotEmpty
+        );
+        require!(self.is_bin_array_bitmap_empty(), LBError::PairNotEmpty);
+        // Reward vault would be left without authority
+        require!(
+            self.reward_infos
+                .iter()
+                .all(|reward_info| !reward_info.initialized()),
+            LBError::PairNotEmpty
+        );
+        Ok(())
+    }
+
+    pub fn is_account_tracking_enabled(&self) -> bool {
+        self.account_tracking_enabled != 0
+    }
+
+    pub fn is_bin_array_bitmap_empty(&self) -> bool {
+        self.bin_array_bitmap.iter().all(|bitmap| *bitmap == 0)
+    }
+
+    pub fn is_whitelist_enabled(&self) -> bool {
+        self.whitelist_enabled != 0
+    }
+
+    /// Zero merkle root disable the whitelist
+    pub fn set_whitelist_merkle_root(&mut self, whitelist_merkle_root: [u8; 32]) {
+        self.whitelist_merkle_root = whitelist_merkle_root;
+        self.whitelist_enabled = (whitelist_merkle_root != [0u8; 32]).into();
+    }
+
     fn require_base_factor_seed(&self) -> bool {
         self.require_base_factor_seed != 0
     }
@@ -505,6 +659,49 @@ impl LbPair {
         Ok(total_fee_rate_cap)
     }
 
+    /// Whether the swap reverses the direction of earlier swaps within the same slot
+    pub fn is_slot_swap_reversal(&self, current_slot: u64, swap_for_y: bool) -> bool {
+        self.v_parameters.last_swap_slot == current_slot
+            && self.last_slot_swap_volume > 0
+            && self.v_parameters.last_swap_for_y != u8::from(swap_for_y)
+    }
+
+    /// Fee adjustment of the swap. Must be computed before the swap, as the slot swap volume is only recorded after the swap.
+    pub fn get_swap_fee_modifier(
+        &self,
+        current_slot: u64,
+        swap_for_y: bool,
+        fee_multiplier_bps: u16,
+    ) -> SwapFeeModifier {
+        let surcharge_bps = if self.is_slot_swap_reversal(current_slot, swap_for_y) {
+            self.parameters.volatility_surcharge_bps
+        } else {
+            0
+        };
+
+        SwapFeeModifier {
+            surcharge_bps,
+            fee_multiplier_bps,
+        }
+    }
+
+    /// Total fee rate of the swap = (total_fee_rate + surcharge_fee_rate) * fee_multiplier
+    pub fn get_swap_total_fee(&self, fee_modifier: &SwapFeeModifier) -> Result<u128> {
+        let surcharge_fee_rate = u128::from(fee_modifier.surcharge_bps)
+            .safe_mul(FEE_PRECISION.into())?
+            .safe_div(BASIS_POINT_MAX as u128)?;
+
+        let total_fee_rate = self
+            .get_base_fee()?
+            .safe_add(self.get_variable_fee()?)?
+            .safe_add(surcharge_fee_rate)?;
+        let total_fee_rate_cap = std::cmp::min(total_fee_rate, MAX_FEE_RATE.into());
+
+        Ok(total_fee_rate_cap
+            .safe_mul(fee_modifier.fee_multiplier_bps.into())?
+            .safe_div(BASIS_POINT_MAX as u128)?)
+    }
+
     #[cfg(test)]
     /// Maximum fee rate
     fn get_max_total_fee(&self) -> Result<u128> {
@@ -532,9 +729,13 @@ impl LbPair {
     }
 
     /// Compute fee from amount, where fee is part of the amount. The result is ceil-ed.
-    pub fn compute_fee_from_amount(&self, amount_with_fees: u64) -> Result<u64> {
+    pub fn compute_fee_from_amount(
+        &self,
+        amount_with_fees: u64,
+        fee_modifier: &SwapFeeModifier,
+    ) -> Result<u64> {
         // total_fee_rate 1e9 unit
-        let total_fee_rate = self.get_total_fee()?;
+        let total_fee_rate = self.get_swap_total_fee(fee_modifier)?;
         // Ceil division
         let fee_amount = u128::from(amount_with_fees)
             .safe_mul(total_fee_rate)?
@@ -550,8 +751,8 @@ impl LbPair {
     /// Solve for fee_amount, equation: (amount + fee_amount) * total_fee_rate / 1e9 = fee_amount
     /// fee_amount = (amount * total_fee_rate) / (1e9 - total_fee_rate)
     /// The result is ceil-ed.
-    pub fn compute_fee(&self, amount: u64) -> Result<u64> {
-        let total_fee_rate = self.get_total_fee()?;
+    pub fn compute_fee(&self, amount: u64, fee_modifier: &SwapFeeModifier) -> Result<u64> {
+        let total_fee_rate = self.get_swap_total_fee(fee_modifier)?;
         let denominator = u128::from(FEE_PRECISION).safe_sub(total_fee_rate)?;
 
         // Ceil division
@@ -567,6 +768,55 @@ impl LbPair {
             .map_err(|_| LBError::TypeCastFailed)?)
     }
 
+    /// Upper bound of the protocol share based on the pair type
+    pub fn get_max_protocol_share(&self) -> Result<u16> {
+        let pair_type = self.pair_type()?;
+        match pair_type {
+            PairType::CustomizablePermissionless => Ok(ILM_PROTOCOL_SHARE),
+            _ => Ok(MAX_PROTOCOL_SHARE),
+        }
+    }
+
+    pub fn set_protocol_share(
+        &mut self,
+        protocol_share: u16,
+        step_down: Option<(u16, u64)>,
+    ) -> Result<()> {
+        require!(
+            protocol_share <= self.get_max_protocol_share()?,
+            LBError::ExcessiveFeeUpdate
+        );
+        self.parameters.protocol_share = protocol_share;
+
+        match step_down {
+            Some((target_protocol_share, step_down_point)) => {
+                self.parameters.target_protocol_share = target_protocol_share;
+                self.parameters.protocol_share_step_down_enabled = 1;
+                self.protocol_share_step_down_point = step_down_point;
+            }
+            None => self.clear_protocol_share_step_down(),
+        }
+
+        Ok(())
+    }
+
+    pub fn clear_protocol_share_step_down(&mut self) {
+        self.parameters.target_protocol_share = 0;
+        self.parameters.protocol_share_step_down_enabled = 0;
+        self.protocol_share_step_down_point = 0;
+    }
+
+    /// Step down the protocol share once the scheduled step down point is reached
+    pub fn update_protocol_share(&mut self, current_point: u64) {
+        if self.parameters.protocol_share_step_down_enabled != 0
+            && current_point >= self.protocol_share_step_down_point
+        {
+            self.parameters.protocol_share = self.parameters.target_protocol_share;
+            self.parameters.target_protocol_share = 0;
+            self.parameters.protocol_share_step_down_enabled = 0;
+        }
+    }
+
     /// Compute protocol fee
     pub fn compute_protocol_fee(&self, fee_amount: u64) -> Result<u64> {
         let protocol_fee = u128::from(fee_amount)
@@ -605,6 +855,31 @@ impl LbPair {
             .update_volatility_accumulator(self.active_id, &self.parameters)
     }
 
+    /// Must be called after the swap. Swap in the tracked direction accumulate the volume, while reversing swap unwind it. The tracking is reset on a new slot.
+    pub fn record_slot_swap(
+        &mut self,
+        current_slot: u64,
+        swap_for_y: bool,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> Result<()> {
+        if self.v_parameters.last_swap_slot != current_slot {
+            self.v_parameters.last_swap_slot = current_slot;
+            self.last_slot_swap_volume = 0;
+        }
+
+        if self.v_parameters.last_swap_for_y == u8::from(swap_for_y)
+            || self.last_slot_swap_volume == 0
+        {
+            self.v_parameters.last_swap_for_y = swap_for_y.into();
+            self.last_slot_swap_volume = self.last_slot_swap_volume.safe_add(amount_out)?;
+        } else {
+            self.last_slot_swap_volume = self.last_slot_swap_volume.saturating_sub(amount_in);
+        }
+
+        Ok(())
+    }
+
     pub fn withdraw_protocol_fee(&mut self, amount_x: u64, amount_y: u64) -> Result<()> {
         self.protocol_fee.amount_x = self.protocol_fee.amount_x.safe_sub(amount_x)?;
         self.protocol_fee.amount_y = self.protocol_fee.amount_y.safe_sub(amount_y)?;
@@ -786,3 +1061,157 @@ impl LbPair {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volatility_surcharge_on_slot_swap_reversal() {
+        let mut lb_pair = LbPair::default();
+        lb_pair.bin_step = 10;
+        lb_pair.parameters.volatility_surcharge_bps = 100;
+
+        let no_surcharge = SwapFeeModifier::default();
+        let base_total_fee = lb_pair.get_swap_total_fee(&no_surcharge).unwrap();
+
+        // First swap of the slot is never surcharged
+        let fee_modifier = lb_pair.get_swap_fee_modifier(10, true, NO_FEE_DISCOUNT_MULTIPLIER_BPS);
+        assert_eq!(fee_modifier, no_surcharge);
+        lb_pair.record_slot_swap(10, true, 1_000, 900).unwrap();
+
+        // Same direction
+        let fee_modifier = lb_pair.get_swap_fee_modifier(10, true, NO_FEE_DISCOUNT_MULTIPLIER_BPS);
+        assert_eq!(fee_modifier.surcharge_bps, 0);
+
+        // Reversal within the same slot
+        let fee_modifier = lb_pair.get_swap_fee_modifier(10, false, NO_FEE_DISCOUNT_MULTIPLIER_BPS);
+        assert_eq!(fee_modifier.surcharge_bps, 100);
+        assert_eq!(
+            lb_pair.get_swap_total_fee(&fee_modifier).unwrap(),
+            base_total_fee + 10_000_000
+        );
+
+        // Reversal unwind part of the volume, the rest is still surcharged
+        lb_pair.record_slot_swap(10, false, 500, 550).unwrap();
+        assert_eq!(lb_pair.last_slot_swap_volume, 400);
+        assert!(lb_pair.is_slot_swap_reversal(10, false));
+
+        lb_pair.record_slot_swap(10, false, 400, 440).unwrap();
+        assert_eq!(lb_pair.last_slot_swap_volume, 0);
+        assert!(!lb_pair.is_slot_swap_reversal(10, false));
+
+        // Tracking is reset on a new slot
+        lb_pair.record_slot_swap(10, true, 1_000, 900).unwrap();
+        assert!(lb_pair.is_slot_swap_reversal(10, false));
+        assert!(!lb_pair.is_slot_swap_reversal(11, false));
+        lb_pair.record_slot_swap(11, false, 1_000, 900).unwrap();
+        assert_eq!(lb_pair.v_parameters.last_swap_for_y, 0);
+        assert_eq!(lb_pair.last_slot_swap_volume, 900);
+
+        // Disabled surcharge
+        lb_pair.parameters.volatility_surcharge_bps = 0;
+        let fee_modifier = lb_pair.get_swap_fee_modifier(11, true, NO_FEE_DISCOUNT_MULTIPLIER_BPS);
+        assert_eq!(fee_modifier.surcharge_bps, 0);
+    }
+
+    #[test]
+    fn test_sync_to_preset_parameter_retain_pair_overrides() {
+        let mut lb_pair = LbPair::default();
+        lb_pair.pair_type = PairType::CustomizablePermissionless.into();
+        lb_pair.parameters.base_factor = 10_000;
+        lb_pair.parameters.volatility_surcharge_bps = 100;
+        lb_pair
+            .set_protocol_share(1_500, Some((500, 1_000)))
+            .unwrap();
+
+        let preset_parameter = PresetParameter {
+            bin_step: 10,
+            base_factor: 8_000,
+            filter_period: 30,
+            decay_period: 600,
+            reduction_factor: 5_000,
+            variable_fee_control: 40_000,
+            max_volatility_accumulator: 350_000,
+            min_bin_id: -100,
+            max_bin_id: 100,
+            protocol_share: 2_000,
+            padding: Default::default(),
+        };
+
+        lb_pair.sync_to_preset_parameter(&preset_parameter, 2);
+
+        assert_eq!(lb_pair.parameters.base_factor, 8_000);
+        assert_eq!(lb_pair.parameters.decay_period, 600);
+        assert_eq!(lb_pair.parameters.protocol_share, 1_500);
+        assert_eq!(lb_pair.parameters.target_protocol_share, 500);
+        assert_eq!(lb_pair.parameters.protocol_share_step_down_enabled, 1);
+        assert_eq!(lb_pair.protocol_share_step_down_point, 1_000);
+        assert_eq!(lb_pair.parameters.volatility_surcharge_bps, 100);
+        assert_eq!(lb_pair.preset_parameter_version, 2);
+    }
+
+    #[test]
+    fn test_explicit_protocol_share_clear_step_down() {
+        let mut lb_pair = LbPair::default();
+        lb_pair.pair_type = PairType::CustomizablePermissionless.into();
+
+        lb_pair
+            .set_protocol_share(1_500, Some((500, 1_000)))
+            .unwrap();
+        assert_eq!(lb_pair.parameters.protocol_share_step_down_enabled, 1);
+
+        lb_pair.set_protocol_share(1_000, None).unwrap();
+        assert_eq!(lb_pair.parameters.protocol_share, 1_000);
+        assert_eq!(lb_pair.parameters.target_protocol_share, 0);
+        assert_eq!(lb_pair.parameters.protocol_share_step_down_enabled, 0);
+        assert_eq!(lb_pair.protocol_share_step_down_point, 0);
+
+        // Cleared schedule never steps down
+        lb_pair.update_protocol_share(u64::MAX);
+        assert_eq!(lb_pair.parameters.protocol_share, 1_000);
+    }
+
+    #[test]
+    fn test_validate_empty() {
+        let mut lb_pair = LbPair::default();
+        lb_pair.account_tracking_enabled = 1;
+        lb_pair.validate_empty().unwrap();
+
+        lb_pair.increase_position_count().unwrap();
+        assert!(lb_pair.validate_empty().is_err());
+        lb_pair.decrease_position_count();
+        lb_pair.validate_empty().unwrap();
+
+        // Positions of untracked pair are unknown
+        lb_pair.account_tracking_enabled = 0;
+        lb_pair.position_count = 1;
+        lb_pair.validate_empty().unwrap();
+
+        let mut with_protocol_fee = lb_pair;
+        with_protocol_fee.protocol_fee.amount_y = 1;
+        assert!(with_protocol_fee.validate_empty().is_err());
+
+        let mut with_liquidity = lb_pair;
+        with_liquidity.bin_array_bitmap[8] = 1;
+        assert!(with_liquidity.validate_empty().is_err());
+
+        let mut with_reward = lb_pair;
+        with_reward.reward_infos[1].mint = Pubkey::new_unique();
+        assert!(with_reward.validate_empty().is_err());
+    }
+
+    #[test]
+    fn test_bin_array_count() {
+        let mut lb_pair = LbPair::default();
+        lb_pair.increase_bin_array_count().unwrap();
+        lb_pair.increase_bin_array_count().unwrap();
+        lb_pair.increase_bin_array_count().unwrap();
+
+        lb_pair.decrease_bin_array_count(2);
+        assert_eq!(lb_pair.bin_array_count, 1);
+        // Bin arrays created before the counter was introduced
+        lb_pair.decrease_bin_array_count(2);
+        assert_eq!(lb_pair.bin_array_count, 0);
+    }
+}
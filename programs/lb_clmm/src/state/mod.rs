@@ -1,7 +1,9 @@
 pub mod bin;
 pub mod bin_array_bitmap_extension;
+pub mod fee_tier;
 pub mod lb_pair;
 pub mod oracle;
 pub mod parameters;
 pub mod position;
 pub mod preset_parameters;
+pub mod whitelisted_wallet;
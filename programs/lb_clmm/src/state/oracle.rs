@@ -212,6 +212,81 @@ impl<'a> DynamicOracle<'a> {
 
         Ok(())
     }
+
+    /// Shrink the observation ring buffer. The buffer is compacted so that the most recent samples, up to the new length, are kept in chronological order starting from index 0.
+    pub fn decrease_length(&mut self, length_to_remove: u64) -> Result<()> {
+        let new_length = self.metadata.length.safe_sub(length_to_remove)?;
+        require!(
+            length_to_remove > 0 && new_length >= DEFAULT_OBSERVATION_LENGTH,
+            LBError::InvalidOracleLength
+        );
+
+        let length = self.metadata.length as usize;
+        let keep_size = std::cmp::min(self.metadata.active_size, new_length) as usize;
+
+        // Collect latest samples, walking backward from the latest sample
+        let mut samples = Vec::with_capacity(keep_size);
+        let mut idx = self.metadata.idx as usize;
+        for _ in 0..keep_size {
+            samples.push(self.observations[idx]);
+            idx = idx.checked_add(length - 1).ok_or(LBError::MathOverflow)? % length;
+        }
+
+        for (observation, sample) in self.observations[..keep_size]
+            .iter_mut()
+            .zip(samples.into_iter().rev())
+        {
+            *observation = sample;
+        }
+
+        for observation in self.observations[keep_size..].iter_mut() {
+            observation.reset();
+        }
+
+        self.metadata.idx = keep_size.saturating_sub(1) as u64;
+        self.metadata.active_size = keep_size as u64;
+        self.metadata.length = new_length;
+
+        Ok(())
+    }
+
+    /// Time weighted average active bin ID over at least `window` seconds. The cumulative active bin ID is extrapolated to the current timestamp using the current active bin ID.
+    pub fn get_twap_active_id(
+        &self,
+        active_id: i32,
+        current_timestamp: i64,
+        window: i64,
+    ) -> Result<i32> {
+        require!(window > 0, LBError::InvalidInput);
+
+        let latest_sample = self
+            .get_latest_sample()
+            .ok_or_else(|| LBError::InsufficientSample)?;
+        let cumulative_active_bin_id =
+            latest_sample.accumulate_active_bin_id(active_id, current_timestamp)?;
+
+        let target_timestamp = current_timestamp.safe_sub(window)?;
+        let length = self.metadata.length as usize;
+        let mut idx = self.metadata.idx as usize;
+
+        // Walk backward from the latest sample until reaching a sample older than the window
+        for _ in 0..self.metadata.active_size {
+            let sample = &self.observations[idx];
+            if sample.initialized() && sample.last_updated_at <= target_timestamp {
+                let elapsed = current_timestamp.safe_sub(sample.last_updated_at)?;
+                let twap_active_id = cumulative_active_bin_id
+                    .safe_sub(sample.cumulative_active_bin_id)?
+                    .safe_div(elapsed.into())?;
+
+                return Ok(twap_active_id
+                    .try_into()
+                    .map_err(|_| LBError::TypeCastFailed)?);
+            }
+            idx = idx.checked_add(length - 1).ok_or(LBError::MathOverflow)? % length;
+        }
+
+        Err(LBError::InsufficientSample.into())
+    }
 }
 
 fn oracle_account_split<'a, 'info>(
@@ -254,3 +329,157 @@ impl<'info> OracleContentLoader<'info> for AccountLoader<'info, Oracle> {
         oracle_account_split(&self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Observation updated at `last_updated_at` with the cumulative active bin ID at that time
+    fn observation(cumulative_active_bin_id: i128, last_updated_at: i64) -> Observation {
+        Observation {
+            cumulative_active_bin_id,
+            created_at: last_updated_at,
+            last_updated_at,
+        }
+    }
+
+    fn oracle(idx: u64, active_size: u64, length: u64) -> Oracle {
+        Oracle {
+            idx,
+            active_size,
+            length,
+        }
+    }
+
+    #[test]
+    fn test_get_twap_active_id_wrapped_buffer() {
+        // Active bin 10 during [100, 200], 20 during [200, 300], 30 during [300, 400], then 40
+        let metadata = RefCell::new(oracle(1, 4, 4));
+        let observations = RefCell::new(vec![
+            observation(4_000, 300),
+            observation(7_000, 400),
+            observation(1_000, 100),
+            observation(2_000, 200),
+        ]);
+        let dynamic_oracle = DynamicOracle::new(
+            metadata.borrow_mut(),
+            RefMut::map(observations.borrow_mut(), |o| o.as_mut_slice()),
+        );
+
+        // Extrapolated to 9_000 at timestamp 450. Latest sample older than the window is at 300.
+        assert_eq!(dynamic_oracle.get_twap_active_id(40, 450, 150).unwrap(), 33);
+        // Walk past the wrap around to the earliest sample at 100
+        assert_eq!(dynamic_oracle.get_twap_active_id(40, 450, 300).unwrap(), 22);
+        assert_eq!(dynamic_oracle.get_twap_active_id(40, 450, 350).unwrap(), 22);
+        // Older than the earliest sample
+        assert!(dynamic_oracle.get_twap_active_id(40, 450, 351).is_err());
+        assert!(dynamic_oracle.get_twap_active_id(40, 450, 0).is_err());
+    }
+
+    #[test]
+    fn test_get_twap_active_id_insufficient_sample() {
+        let metadata = RefCell::new(oracle(0, 0, 4));
+        let observations = RefCell::new(vec![Observation::default(); 4]);
+        {
+            let dynamic_oracle = DynamicOracle::new(
+                metadata.borrow_mut(),
+                RefMut::map(observations.borrow_mut(), |o| o.as_mut_slice()),
+            );
+            assert!(dynamic_oracle.get_twap_active_id(10, 450, 100).is_err());
+        }
+
+        // Partially filled buffer, only history since 300
+        *metadata.borrow_mut() = oracle(1, 2, 4);
+        observations.borrow_mut()[0] = observation(3_000, 300);
+        observations.borrow_mut()[1] = observation(4_000, 400);
+        let dynamic_oracle = DynamicOracle::new(
+            metadata.borrow_mut(),
+            RefMut::map(observations.borrow_mut(), |o| o.as_mut_slice()),
+        );
+        assert_eq!(dynamic_oracle.get_twap_active_id(10, 450, 150).unwrap(), 10);
+        assert!(dynamic_oracle.get_twap_active_id(10, 450, 151).is_err());
+    }
+
+    #[test]
+    fn test_decrease_length_wrapped_full_buffer() {
+        // Earliest sample at index 2, latest at index 1
+        let length = DEFAULT_OBSERVATION_LENGTH + 3;
+        let metadata = RefCell::new(oracle(1, length, length));
+        let observations = RefCell::new(vec![Observation::default(); length as usize]);
+        for k in 0..length {
+            let idx = ((2 + k) % length) as usize;
+            let timestamp = (k as i64 + 1) * 10;
+            observations.borrow_mut()[idx] = observation(timestamp.into(), timestamp);
+        }
+
+        let mut dynamic_oracle = DynamicOracle::new(
+            metadata.borrow_mut(),
+            RefMut::map(observations.borrow_mut(), |o| o.as_mut_slice()),
+        );
+        assert!(dynamic_oracle.decrease_length(0).is_err());
+        assert!(dynamic_oracle.decrease_length(4).is_err());
+        dynamic_oracle.decrease_length(3).unwrap();
+
+        assert_eq!(dynamic_oracle.metadata.idx, DEFAULT_OBSERVATION_LENGTH - 1);
+        assert_eq!(
+            dynamic_oracle.metadata.active_size,
+            DEFAULT_OBSERVATION_LENGTH
+        );
+        assert_eq!(dynamic_oracle.metadata.length, DEFAULT_OBSERVATION_LENGTH);
+
+        // The 3 earliest samples are dropped, the rest kept in chronological order
+        for (i, sample) in dynamic_oracle.observations[..DEFAULT_OBSERVATION_LENGTH as usize]
+            .iter()
+            .enumerate()
+        {
+            assert_eq!(sample.last_updated_at, (i as i64 + 4) * 10);
+        }
+        assert!(
+            !dynamic_oracle.observations[DEFAULT_OBSERVATION_LENGTH as usize..]
+                .iter()
+                .any(|sample| sample.initialized())
+        );
+        assert_eq!(
+            dynamic_oracle.get_latest_sample().unwrap().last_updated_at,
+            length as i64 * 10
+        );
+        assert_eq!(
+            dynamic_oracle
+                .get_earliest_sample()
+                .unwrap()
+                .last_updated_at,
+            40
+        );
+    }
+
+    #[test]
+    fn test_decrease_length_partially_filled_buffer() {
+        let length = DEFAULT_OBSERVATION_LENGTH + 5;
+        let metadata = RefCell::new(oracle(3, 4, length));
+        let observations = RefCell::new(vec![Observation::default(); length as usize]);
+        for i in 0..4 {
+            let timestamp = (i as i64 + 1) * 10;
+            observations.borrow_mut()[i] = observation(timestamp.into(), timestamp);
+        }
+
+        let mut dynamic_oracle = DynamicOracle::new(
+            metadata.borrow_mut(),
+            RefMut::map(observations.borrow_mut(), |o| o.as_mut_slice()),
+        );
+        dynamic_oracle.decrease_length(5).unwrap();
+
+        assert_eq!(dynamic_oracle.metadata.idx, 3);
+        assert_eq!(dynamic_oracle.metadata.active_size, 4);
+        assert_eq!(dynamic_oracle.metadata.length, DEFAULT_OBSERVATION_LENGTH);
+        for (i, sample) in dynamic_oracle.observations[..4].iter().enumerate() {
+            assert_eq!(sample.last_updated_at, (i as i64 + 1) * 10);
+        }
+        assert!(!dynamic_oracle.observations[4..]
+            .iter()
+            .any(|sample| sample.initialized()));
+
+        // Active bin 1 since the first sample
+        assert_eq!(dynamic_oracle.get_twap_active_id(1, 50, 40).unwrap(), 1);
+    }
+}
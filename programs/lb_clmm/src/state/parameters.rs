@@ -1,4 +1,6 @@
-use crate::constants::{BASIS_POINT_MAX, MAX_BASE_FACTOR_STEP, MAX_PROTOCOL_SHARE};
+use crate::constants::{
+    BASIS_POINT_MAX, MAX_BASE_FACTOR_STEP, MAX_PROTOCOL_SHARE, MAX_VOLATILITY_SURCHARGE_BPS,
+};
 use crate::instructions::admin::update_fee_parameters::FeeParameter;
 use crate::{errors::LBError, math::safe_math::SafeMath};
 use anchor_lang::prelude::*;
@@ -25,8 +27,14 @@ pub struct StaticParameters {
     pub max_bin_id: i32,
     /// Portion of swap fees retained by the protocol by controlling protocol_share parameter. protocol_swap_fee = protocol_share * total_swap_fee
     pub protocol_share: u16,
+    /// Extra fee rate in bps charged on swap which reverses the direction of earlier swaps within the same slot. 0 to disable.
+    pub volatility_surcharge_bps: u16,
+    /// Protocol share in bps to step down to once the scheduled step down point is reached. Only applicable for customizable permissionless pair.
+    pub target_protocol_share: u16,
+    /// Whether the protocol share step down is scheduled. 0 = No, 1 = Yes
+    pub protocol_share_step_down_enabled: u8,
     /// Padding for bytemuck safe alignment
-    pub _padding: [u8; 6],
+    pub _padding: [u8; 1],
 }
 
 impl StaticParameters {
@@ -55,8 +63,14 @@ impl StaticParameters {
             LBError::ExcessiveFeeUpdate
         );
 
+        require!(
+            parameter.volatility_surcharge_bps <= MAX_VOLATILITY_SURCHARGE_BPS,
+            LBError::ExcessiveFeeUpdate
+        );
+
         self.protocol_share = parameter.protocol_share;
         self.base_factor = parameter.base_factor;
+        self.volatility_surcharge_bps = parameter.volatility_surcharge_bps;
 
         Ok(())
     }
@@ -97,7 +111,10 @@ impl Default for StaticParameters {
             variable_fee_control: 40_000,
             protocol_share: 1_000,
             max_volatility_accumulator: 350_000, // Capped at 35 bin crossed. 350_000 / 10_000 (bps unit) = 35 delta bin
-            _padding: [0u8; 6],
+            volatility_surcharge_bps: 0,
+            target_protocol_share: 0,
+            protocol_share_step_down_enabled: 0,
+            _padding: [0u8; 1],
             max_bin_id: i32::MAX,
             min_bin_id: i32::MIN,
         }
@@ -115,12 +132,14 @@ pub struct VariableParameters {
     pub volatility_reference: u32,
     /// Active bin id of last swap.
     pub index_reference: i32,
+    /// Direction of the swaps tracked in last_swap_slot. 1 = swap for y, 0 = swap for x
+    pub last_swap_for_y: u8,
     /// Padding for bytemuck safe alignment
-    pub _padding: [u8; 4],
+    pub _padding: [u8; 3],
     /// Last timestamp the variable parameters was updated
     pub last_update_timestamp: i64,
-    /// Padding for bytemuck safe alignment
-    pub _padding_1: [u8; 8],
+    /// Slot of the last swap
+    pub last_swap_slot: u64,
 }
 
 impl VariableParameters {
@@ -1,6 +1,6 @@
 use super::bin::Bin;
 use crate::{
-    constants::{MAX_BIN_PER_POSITION, NUM_REWARDS},
+    constants::{BASIS_POINT_MAX, MAX_BIN_PER_POSITION, NUM_REWARDS},
     errors::LBError,
     manager::bin_array_manager::BinArrayManager,
     math::{
@@ -9,6 +9,7 @@ use crate::{
     },
 };
 use anchor_lang::prelude::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use num_traits::Zero;
 use std::cell::Ref;
 
@@ -74,8 +75,16 @@ pub struct PositionV2 {
     pub _padding_0: u8,
     /// Address is able to claim fee in this position, only valid for bootstrap_liquidity_position
     pub fee_owner: Pubkey,
+    /// Padding for bytemuck safe alignment
+    pub _padding_1: [u8; 7],
+    /// Stop-loss / take-profit trigger of the position. Executed by keeper.
+    pub trigger: PositionTrigger,
+    /// Range order side of one-sided position. Check RangeOrderSide enum.
+    pub range_order_side: u8,
+    /// Payer of the position rental. Zero for position created before the field was introduced, the owner is the rent payer.
+    pub rent_payer: Pubkey,
     /// Reserved space for future use
-    pub _reserved: [u8; 87],
+    pub _reserved: [u8; 31],
 }
 
 impl Default for PositionV2 {
@@ -96,11 +105,60 @@ impl Default for PositionV2 {
             lock_release_point: 0,
             fee_owner: Pubkey::default(),
             _padding_0: 0,
-            _reserved: [0u8; 87],
+            _padding_1: [0u8; 7],
+            trigger: PositionTrigger::default(),
+            range_order_side: RangeOrderSide::None.into(),
+            rent_payer: Pubkey::default(),
+            _reserved: [0u8; 31],
         }
     }
 }
 
+/// Range order side. 0 = None, 1 = Ask (sell token X), 2 = Bid (sell token Y)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum RangeOrderSide {
+    None,
+    Ask,
+    Bid,
+}
+
+#[zero_copy]
+#[derive(Default, Debug, InitSpace, PartialEq)]
+/// Withdraw all liquidity of the position to the owner when the price bin reach the threshold
+pub struct PositionTrigger {
+    /// Fire when the price bin <= lower_bin_id. Stop-loss / take-profit depending on the position side.
+    pub lower_bin_id: i32,
+    /// Fire when the price bin >= upper_bin_id. Stop-loss / take-profit depending on the position side.
+    pub upper_bin_id: i32,
+    /// TWAP window in seconds. 0 to use the active bin as price bin.
+    pub twap_window: u32,
+    /// Portion of the withdrawn amount paid to the keeper
+    pub keeper_bounty_bps: u16,
+    /// 1 = enabled, 0 = disabled
+    pub enabled: u8,
+    /// Padding for bytemuck safe alignment
+    pub _padding: u8,
+}
+
+impl PositionTrigger {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled != 0
+    }
+
+    pub fn is_fired(&self, price_bin_id: i32) -> bool {
+        price_bin_id <= self.lower_bin_id || price_bin_id >= self.upper_bin_id
+    }
+
+    pub fn compute_keeper_bounty(&self, amount: u64) -> Result<u64> {
+        let bounty = u128::from(amount)
+            .safe_mul(self.keeper_bounty_bps.into())?
+            .safe_div(BASIS_POINT_MAX as u128)?;
+
+        Ok(bounty.try_into().map_err(|_| LBError::TypeCastFailed)?)
+    }
+}
+
 #[zero_copy]
 #[derive(Default, Debug, AnchorDeserialize, AnchorSerialize, InitSpace, PartialEq)]
 pub struct FeeInfo {
@@ -128,6 +186,7 @@ impl PositionV2 {
         current_time: i64,
         lock_release_point: u64,
         fee_owner: Pubkey,
+        rent_payer: Pubkey,
     ) -> Result<()> {
         self.lb_pair = lb_pair;
         self.owner = owner;
@@ -143,6 +202,9 @@ impl PositionV2 {
         self.lock_release_point = lock_release_point;
 
         self.fee_owner = fee_owner;
+        self.trigger = PositionTrigger::default();
+        self.range_order_side = RangeOrderSide::None.into();
+        self.rent_payer = rent_payer;
 
         Ok(())
     }
@@ -363,4 +425,153 @@ impl PositionV2 {
     pub fn is_liquidity_locked(&self, current_point: u64) -> bool {
         current_point < self.lock_release_point
     }
+
+    pub fn range_order_side(&self) -> Result<RangeOrderSide> {
+        RangeOrderSide::try_from(self.range_order_side).map_err(|_| LBError::TypeCastFailed.into())
+    }
+
+    /// Range order side of the position. The position must be one-sided, and fully outside of the active bin.
+    pub fn get_range_order_side(&self, active_id: i32) -> Result<RangeOrderSide> {
+        if self.lower_bin_id > active_id {
+            Ok(RangeOrderSide::Ask)
+        } else if self.upper_bin_id < active_id {
+            Ok(RangeOrderSide::Bid)
+        } else {
+            Err(LBError::InvalidRangeOrder.into())
+        }
+    }
+
+    pub fn get_rent_payer(&self) -> Pubkey {
+        if self.rent_payer == Pubkey::default() {
+            self.owner
+        } else {
+            self.rent_payer
+        }
+    }
+
+    /// Range order is filled when the active bin has fully crossed the position range.
+    pub fn is_range_order_crossed(&self, active_id: i32) -> Result<bool> {
+        match self.range_order_side()? {
+            RangeOrderSide::Ask => Ok(active_id > self.upper_bin_id),
+            RangeOrderSide::Bid => Ok(active_id < self.lower_bin_id),
+            RangeOrderSide::None => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MAX_KEEPER_BOUNTY_BPS;
+    use crate::instructions::position_trigger::set_position_trigger::PositionTriggerParameter;
+
+    fn trigger(keeper_bounty_bps: u16) -> PositionTrigger {
+        PositionTrigger {
+            lower_bin_id: -100,
+            upper_bin_id: 100,
+            keeper_bounty_bps,
+            enabled: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_trigger_is_fired() {
+        let trigger = trigger(0);
+
+        assert!(trigger.is_fired(-100));
+        assert!(trigger.is_fired(-101));
+        assert!(trigger.is_fired(100));
+        assert!(trigger.is_fired(101));
+        assert!(!trigger.is_fired(-99));
+        assert!(!trigger.is_fired(0));
+        assert!(!trigger.is_fired(99));
+    }
+
+    #[test]
+    fn test_compute_keeper_bounty() {
+        assert_eq!(trigger(0).compute_keeper_bounty(1_000_000).unwrap(), 0);
+        assert_eq!(trigger(25).compute_keeper_bounty(1_000_000).unwrap(), 2_500);
+        // Rounded down
+        assert_eq!(trigger(25).compute_keeper_bounty(399).unwrap(), 0);
+
+        let max_trigger = trigger(MAX_KEEPER_BOUNTY_BPS);
+        assert_eq!(
+            max_trigger.compute_keeper_bounty(u64::MAX).unwrap(),
+            (u128::from(u64::MAX) * u128::from(MAX_KEEPER_BOUNTY_BPS) / BASIS_POINT_MAX as u128)
+                as u64
+        );
+    }
+
+    #[test]
+    fn test_keeper_bounty_capped() {
+        let parameter = |keeper_bounty_bps| PositionTriggerParameter {
+            lower_bin_id: -100,
+            upper_bin_id: 100,
+            twap_window: 0,
+            keeper_bounty_bps,
+        };
+
+        assert!(parameter(MAX_KEEPER_BOUNTY_BPS).validate().is_ok());
+        assert!(parameter(MAX_KEEPER_BOUNTY_BPS + 1).validate().is_err());
+    }
+
+    fn position(lower_bin_id: i32, upper_bin_id: i32) -> PositionV2 {
+        PositionV2 {
+            owner: Pubkey::new_unique(),
+            lower_bin_id,
+            upper_bin_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_range_order_side() {
+        let mut position = position(10, 20);
+
+        assert_eq!(position.range_order_side().unwrap(), RangeOrderSide::None);
+        position.range_order_side = 3;
+        assert!(position.range_order_side().is_err());
+
+        // Above the active bin sell token X, below sell token Y
+        assert_eq!(
+            position.get_range_order_side(9).unwrap(),
+            RangeOrderSide::Ask
+        );
+        assert_eq!(
+            position.get_range_order_side(21).unwrap(),
+            RangeOrderSide::Bid
+        );
+        // Active bin within the range is not one-sided
+        assert!(position.get_range_order_side(10).is_err());
+        assert!(position.get_range_order_side(15).is_err());
+        assert!(position.get_range_order_side(20).is_err());
+    }
+
+    #[test]
+    fn test_is_range_order_crossed() {
+        let mut position = position(10, 20);
+        assert!(!position.is_range_order_crossed(30).unwrap());
+
+        position.range_order_side = RangeOrderSide::Ask.into();
+        assert!(!position.is_range_order_crossed(5).unwrap());
+        assert!(!position.is_range_order_crossed(20).unwrap());
+        assert!(position.is_range_order_crossed(21).unwrap());
+
+        position.range_order_side = RangeOrderSide::Bid.into();
+        assert!(!position.is_range_order_crossed(25).unwrap());
+        assert!(!position.is_range_order_crossed(10).unwrap());
+        assert!(position.is_range_order_crossed(9).unwrap());
+    }
+
+    #[test]
+    fn test_get_rent_payer() {
+        let mut position = position(10, 20);
+        // Position created before the rent payer was stored
+        assert_eq!(position.get_rent_payer(), position.owner);
+
+        let rent_payer = Pubkey::new_unique();
+        position.rent_payer = rent_payer;
+        assert_eq!(position.get_rent_payer(), rent_payer);
+    }
 }
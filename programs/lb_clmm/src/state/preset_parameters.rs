@@ -1,7 +1,9 @@
 use crate::constants::{BASIS_POINT_MAX, MAX_PROTOCOL_SHARE, U24_MAX};
 use crate::errors::LBError;
 use crate::math::price_math::get_price_from_id;
+use crate::math::safe_math::SafeMath;
 use anchor_lang::prelude::*;
+use std::io::Read;
 
 use super::parameters::StaticParameters;
 
@@ -28,6 +30,36 @@ pub struct PresetParameter {
     pub max_bin_id: i32,
     /// Portion of swap fees retained by the protocol by controlling protocol_share parameter. protocol_swap_fee = protocol_share * total_swap_fee
     pub protocol_share: u16,
+    /// Padding of the preset parameter. Hold the version of the preset parameter.
+    pub padding: PresetParameterPadding,
+}
+
+#[derive(AnchorSerialize, InitSpace, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresetParameterPadding {
+    /// Incremented on every update of the preset parameter. Pair synced to the preset record the version.
+    pub version: u64,
+    /// Reserved space for future use
+    pub _reserved: [u8; 24],
+}
+
+/// Preset parameter created before the padding was introduced is shorter, and read as zeroed padding. The account is reallocated on the next update.
+impl AnchorDeserialize for PresetParameterPadding {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut data = Vec::with_capacity(Self::INIT_SPACE);
+        reader
+            .take(Self::INIT_SPACE as u64)
+            .read_to_end(&mut data)?;
+
+        if data.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut data = data.as_slice();
+        Ok(Self {
+            version: u64::deserialize(&mut data)?,
+            _reserved: <[u8; 24]>::deserialize(&mut data)?,
+        })
+    }
 }
 
 impl PresetParameter {
@@ -80,6 +112,16 @@ impl PresetParameter {
         );
     }
 
+    /// Bump the version of the preset parameter. Pair created from the preset can be synced to the new version.
+    pub fn bump_version(&mut self) -> Result<()> {
+        self.padding.version = self.padding.version.safe_add(1)?;
+        Ok(())
+    }
+
+    pub fn version(&self) -> u64 {
+        self.padding.version
+    }
+
     pub fn validate(&self) -> Result<()> {
         require!(
             self.bin_step <= BASIS_POINT_MAX as u16,
@@ -143,7 +185,50 @@ impl PresetParameter {
             reduction_factor: self.reduction_factor,
             protocol_share: self.protocol_share,
             max_volatility_accumulator: self.max_volatility_accumulator,
-            _padding: [0u8; 6],
+            volatility_surcharge_bps: 0,
+            target_protocol_share: 0,
+            protocol_share_step_down_enabled: 0,
+            _padding: [0u8; 1],
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_preset_parameter_without_padding() {
+        let mut preset_parameter = PresetParameter {
+            bin_step: 10,
+            base_factor: 10_000,
+            filter_period: 30,
+            decay_period: 600,
+            reduction_factor: 5_000,
+            variable_fee_control: 40_000,
+            max_volatility_accumulator: 350_000,
+            min_bin_id: -443636,
+            max_bin_id: 443636,
+            protocol_share: 0,
+            padding: PresetParameterPadding::default(),
+        };
+        preset_parameter.bump_version().unwrap();
+
+        let mut data = vec![];
+        preset_parameter.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + PresetParameter::INIT_SPACE);
+
+        let deserialized = PresetParameter::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(deserialized.version(), 1);
+
+        // Preset parameter created before the padding was introduced
+        let legacy_len = data.len() - PresetParameterPadding::INIT_SPACE;
+        let legacy = PresetParameter::try_deserialize(&mut &data[..legacy_len]).unwrap();
+        assert_eq!(legacy.version(), 0);
+        assert_eq!(legacy.base_factor, 10_000);
+        assert_eq!(legacy.protocol_share, 0);
+
+        // Partially written padding is invalid
+        assert!(PresetParameter::try_deserialize(&mut &data[..legacy_len + 4]).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+use crate::errors::LBError;
+use crate::instructions::initialize_whitelisted_wallet::WhitelistedWalletParameter;
+use crate::math::safe_math::SafeMath;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Wallet proven to be in the whitelist merkle tree of a permission pair. Act as the proof for early access during the pre-activation window.
+pub struct WhitelistedWallet {
+    /// The pair the wallet is whitelisted for
+    pub lb_pair: Pubkey,
+    /// Whitelisted wallet
+    pub wallet: Pubkey,
+    /// Merkle root the wallet was proven against. Whitelisted wallet is invalidated when the pair merkle root changed, until it is refreshed against the new root.
+    pub merkle_root: [u8; 32],
+    /// Maximum amount of token X the wallet can swap in during the pre-activation window
+    pub max_swap_amount_x: u64,
+    /// Maximum amount of token Y the wallet can swap in during the pre-activation window
+    pub max_swap_amount_y: u64,
+    /// Maximum amount of token X the wallet can deposit during the pre-activation window
+    pub max_deposit_amount_x: u64,
+    /// Maximum amount of token Y the wallet can deposit during the pre-activation window
+    pub max_deposit_amount_y: u64,
+    /// Amount of token X swapped in during the pre-activation window
+    pub swapped_amount_x: u64,
+    /// Amount of token Y swapped in during the pre-activation window
+    pub swapped_amount_y: u64,
+    /// Amount of token X deposited during the pre-activation window
+    pub deposited_amount_x: u64,
+    /// Amount of token Y deposited during the pre-activation window
+    pub deposited_amount_y: u64,
+    /// Reserved space for future use
+    pub _reserved: [u8; 32],
+}
+
+impl WhitelistedWallet {
+    pub fn init(
+        &mut self,
+        lb_pair: Pubkey,
+        wallet: Pubkey,
+        merkle_root: [u8; 32],
+        parameter: &WhitelistedWalletParameter,
+    ) {
+        self.lb_pair = lb_pair;
+        self.wallet = wallet;
+        self.update(merkle_root, parameter);
+    }
+
+    /// Re-prove the wallet against the latest merkle root. Amount swapped and deposited so far still count toward the new caps.
+    pub fn update(&mut self, merkle_root: [u8; 32], parameter: &WhitelistedWalletParameter) {
+        self.merkle_root = merkle_root;
+        self.max_swap_amount_x = parameter.max_swap_amount_x;
+        self.max_swap_amount_y = parameter.max_swap_amount_y;
+        self.max_deposit_amount_x = parameter.max_deposit_amount_x;
+        self.max_deposit_amount_y = parameter.max_deposit_amount_y;
+    }
+
+    pub fn is_valid(&self, wallet: Pubkey, merkle_root: [u8; 32]) -> bool {
+        self.wallet == wallet && self.merkle_root == merkle_root
+    }
+
+    /// Accumulate the swap amount of the input token. Token X is swapped in when swap_for_y.
+    pub fn accumulate_swap_amount(&mut self, swap_for_y: bool, amount_in: u64) -> Result<()> {
+        let (swapped_amount, max_swap_amount) = if swap_for_y {
+            (&mut self.swapped_amount_x, self.max_swap_amount_x)
+        } else {
+            (&mut self.swapped_amount_y, self.max_swap_amount_y)
+        };
+
+        *swapped_amount = swapped_amount.safe_add(amount_in)?;
+        require!(
+            *swapped_amount <= max_swap_amount,
+            LBError::ExceededWhitelistCap
+        );
+        Ok(())
+    }
+
+    pub fn accumulate_deposit_amount(&mut self, amount_x: u64, amount_y: u64) -> Result<()> {
+        self.deposited_amount_x = self.deposited_amount_x.safe_add(amount_x)?;
+        self.deposited_amount_y = self.deposited_amount_y.safe_add(amount_y)?;
+        require!(
+            self.deposited_amount_x <= self.max_deposit_amount_x
+                && self.deposited_amount_y <= self.max_deposit_amount_y,
+            LBError::ExceededWhitelistCap
+        );
+        Ok(())
+    }
+}
+
+/// Whitelisted wallet of the sender is passed as the first remaining account. The remaining accounts are advanced when it is present.
+pub fn load_whitelisted_wallet<'c, 'info>(
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    lb_pair: Pubkey,
+    sender: Pubkey,
+) -> Result<Option<AccountLoader<'info, WhitelistedWallet>>> {
+    let Some(account_info) = remaining_accounts.first() else {
+        return Ok(None);
+    };
+
+    let is_whitelisted_wallet = account_info.owner.eq(&crate::ID)
+        && account_info
+            .try_borrow_data()?
+            .starts_with(&WhitelistedWallet::discriminator());
+
+    if !is_whitelisted_wallet {
+        return Ok(None);
+    }
+
+    let whitelisted_wallet = AccountLoader::<WhitelistedWallet>::try_from(account_info)?;
+    {
+        let state = whitelisted_wallet.load()?;
+        require!(state.lb_pair == lb_pair, LBError::InvalidInput);
+        require!(state.wallet == sender, LBError::UnauthorizedAccess);
+    }
+
+    *remaining_accounts = &remaining_accounts[1..];
+
+    Ok(Some(whitelisted_wallet))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn parameter() -> WhitelistedWalletParameter {
+        WhitelistedWalletParameter {
+            max_swap_amount_x: 1_000,
+            max_swap_amount_y: 2_000,
+            max_deposit_amount_x: 500,
+            max_deposit_amount_y: 800,
+            proof: vec![],
+        }
+    }
+
+    fn whitelisted_wallet(wallet: Pubkey, merkle_root: [u8; 32]) -> WhitelistedWallet {
+        let mut whitelisted_wallet: WhitelistedWallet = bytemuck::Zeroable::zeroed();
+        whitelisted_wallet.init(Pubkey::new_unique(), wallet, merkle_root, &parameter());
+        whitelisted_wallet
+    }
+
+    #[test]
+    fn test_is_valid_against_wallet_and_merkle_root() {
+        let wallet = Pubkey::new_unique();
+        let whitelisted_wallet = whitelisted_wallet(wallet, [1u8; 32]);
+
+        assert!(whitelisted_wallet.is_valid(wallet, [1u8; 32]));
+        assert!(!whitelisted_wallet.is_valid(Pubkey::new_unique(), [1u8; 32]));
+        // Invalidated once the pair merkle root changed
+        assert!(!whitelisted_wallet.is_valid(wallet, [2u8; 32]));
+    }
+
+    #[test]
+    fn test_accumulate_up_to_cap() {
+        let mut whitelisted_wallet = whitelisted_wallet(Pubkey::new_unique(), [1u8; 32]);
+
+        // Swap X for Y consume the token X cap only
+        whitelisted_wallet
+            .accumulate_swap_amount(true, 600)
+            .unwrap();
+        whitelisted_wallet
+            .accumulate_swap_amount(true, 400)
+            .unwrap();
+        {
+            let mut capped = whitelisted_wallet;
+            assert!(capped.accumulate_swap_amount(true, 1).is_err());
+        }
+        whitelisted_wallet
+            .accumulate_swap_amount(false, 2_000)
+            .unwrap();
+        {
+            let mut capped = whitelisted_wallet;
+            assert!(capped.accumulate_swap_amount(false, 1).is_err());
+        }
+
+        whitelisted_wallet
+            .accumulate_deposit_amount(500, 0)
+            .unwrap();
+        {
+            let mut capped = whitelisted_wallet;
+            assert!(capped.accumulate_deposit_amount(1, 0).is_err());
+        }
+        whitelisted_wallet
+            .accumulate_deposit_amount(0, 800)
+            .unwrap();
+        {
+            let mut capped = whitelisted_wallet;
+            assert!(capped.accumulate_deposit_amount(0, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn test_update_keep_accumulated_amount() {
+        let wallet = Pubkey::new_unique();
+        let mut whitelisted_wallet = whitelisted_wallet(wallet, [1u8; 32]);
+        whitelisted_wallet
+            .accumulate_swap_amount(true, 1_000)
+            .unwrap();
+        whitelisted_wallet
+            .accumulate_deposit_amount(500, 0)
+            .unwrap();
+
+        let mut parameter = parameter();
+        parameter.max_swap_amount_x = 1_500;
+        whitelisted_wallet.update([2u8; 32], &parameter);
+
+        assert!(whitelisted_wallet.is_valid(wallet, [2u8; 32]));
+        assert!(!whitelisted_wallet.is_valid(wallet, [1u8; 32]));
+        assert_eq!(whitelisted_wallet.swapped_amount_x, 1_000);
+        whitelisted_wallet
+            .accumulate_swap_amount(true, 500)
+            .unwrap();
+        {
+            let mut capped = whitelisted_wallet;
+            assert!(capped.accumulate_swap_amount(true, 1).is_err());
+        }
+        assert!(whitelisted_wallet.accumulate_deposit_amount(1, 0).is_err());
+    }
+
+    #[test]
+    fn test_load_whitelisted_wallet() {
+        let lb_pair = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+
+        let mut state: WhitelistedWallet = bytemuck::Zeroable::zeroed();
+        state.init(lb_pair, wallet, [1u8; 32], &parameter());
+        let mut data = WhitelistedWallet::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&state));
+
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let whitelisted_wallet_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        let other_key = Pubkey::new_unique();
+        let mut other_lamports = 0;
+        let mut other_data = vec![0u8; 8];
+        let other_info = AccountInfo::new(
+            &other_key,
+            false,
+            true,
+            &mut other_lamports,
+            &mut other_data,
+            &owner,
+            false,
+            0,
+        );
+
+        let accounts = [whitelisted_wallet_info, other_info];
+
+        // Not a whitelisted wallet, remaining accounts untouched
+        let mut remaining_accounts = &accounts[1..];
+        assert!(
+            load_whitelisted_wallet(&mut remaining_accounts, lb_pair, wallet)
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(remaining_accounts.len(), 1);
+
+        // Belong to another wallet
+        let mut remaining_accounts = &accounts[..];
+        assert!(
+            load_whitelisted_wallet(&mut remaining_accounts, lb_pair, Pubkey::new_unique())
+                .is_err()
+        );
+
+        let mut remaining_accounts = &accounts[..];
+        let loader = load_whitelisted_wallet(&mut remaining_accounts, lb_pair, wallet)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loader.load().unwrap().wallet, wallet);
+        assert_eq!(remaining_accounts.len(), 1);
+        assert_eq!(remaining_accounts[0].key(), other_key);
+    }
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+
+/// Prefix to distinguish leaf from intermediate node. Prevent second preimage attack.
+const LEAF_PREFIX: &[u8] = &[0];
+const INTERMEDIATE_PREFIX: &[u8] = &[1];
+
+/// Leaf of the whitelist merkle tree. leaf = keccak(0 || wallet || max_swap_amount_x || max_swap_amount_y || max_deposit_amount_x || max_deposit_amount_y)
+pub fn get_whitelist_leaf(
+    wallet: Pubkey,
+    max_swap_amount_x: u64,
+    max_swap_amount_y: u64,
+    max_deposit_amount_x: u64,
+    max_deposit_amount_y: u64,
+) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        wallet.as_ref(),
+        &max_swap_amount_x.to_le_bytes(),
+        &max_swap_amount_y.to_le_bytes(),
+        &max_deposit_amount_x.to_le_bytes(),
+        &max_deposit_amount_y.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Verify the leaf belongs to the merkle tree. The pair of nodes are sorted before hashing.
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof.iter().fold(leaf, |node, proof_node| {
+        if node <= *proof_node {
+            hashv(&[INTERMEDIATE_PREFIX, &node, proof_node]).to_bytes()
+        } else {
+            hashv(&[INTERMEDIATE_PREFIX, proof_node, &node]).to_bytes()
+        }
+    });
+
+    computed_root == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        hashv(&[INTERMEDIATE_PREFIX, &a, &b]).to_bytes()
+    }
+
+    fn hash_sorted_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hash_pair(a, b)
+        } else {
+            hash_pair(b, a)
+        }
+    }
+
+    /// Tree of 4 leaves. Returns the leaves, the two intermediate nodes and the root.
+    fn tree() -> (Vec<[u8; 32]>, [[u8; 32]; 2], [u8; 32]) {
+        let leaves: Vec<[u8; 32]> = (0..4)
+            .map(|i| get_whitelist_leaf(Pubkey::new_unique(), i, i + 1, i + 2, i + 3))
+            .collect();
+        let nodes = [
+            hash_sorted_pair(leaves[0], leaves[1]),
+            hash_sorted_pair(leaves[2], leaves[3]),
+        ];
+        let root = hash_sorted_pair(nodes[0], nodes[1]);
+        (leaves, nodes, root)
+    }
+
+    #[test]
+    fn test_verify_proof_valid() {
+        let (leaves, nodes, root) = tree();
+
+        assert!(verify_proof(&[leaves[1], nodes[1]], root, leaves[0]));
+        assert!(verify_proof(&[leaves[0], nodes[1]], root, leaves[1]));
+        assert!(verify_proof(&[leaves[3], nodes[0]], root, leaves[2]));
+        assert!(verify_proof(&[leaves[2], nodes[0]], root, leaves[3]));
+    }
+
+    #[test]
+    fn test_verify_proof_wrong_leaf() {
+        let (leaves, nodes, root) = tree();
+        let proof = [leaves[1], nodes[1]];
+
+        // Same wallet with different caps
+        let wallet = Pubkey::new_unique();
+        let leaf = get_whitelist_leaf(wallet, 0, 1, 2, 3);
+        let tampered_leaf = get_whitelist_leaf(wallet, 0, 1, 2, 4);
+        let node = hash_sorted_pair(leaf, leaves[1]);
+        let root_with_wallet = hash_sorted_pair(node, nodes[1]);
+
+        assert!(verify_proof(&proof, root_with_wallet, leaf));
+        assert!(!verify_proof(&proof, root_with_wallet, tampered_leaf));
+        assert!(!verify_proof(&proof, root, leaf));
+        // Leaf proven against another root
+        assert!(!verify_proof(&proof, root, leaves[2]));
+    }
+
+    #[test]
+    fn test_verify_proof_sibling_ordering() {
+        let (leaves, nodes, root) = tree();
+
+        // Pair of nodes are sorted before hashing, the sibling side does not matter
+        let (low, high) = if leaves[0] <= leaves[1] {
+            (leaves[0], leaves[1])
+        } else {
+            (leaves[1], leaves[0])
+        };
+        assert!(verify_proof(&[high, nodes[1]], root, low));
+        assert!(verify_proof(&[low, nodes[1]], root, high));
+
+        // Tree hashed without sorting is not accepted
+        let unsorted_root = hash_pair(hash_pair(high, low), nodes[1]);
+        assert!(!verify_proof(&[low, nodes[1]], unsorted_root, high));
+
+        // Proof nodes must be in the order from leaf to root
+        assert!(!verify_proof(&[nodes[1], leaves[1]], root, leaves[0]));
+    }
+}
@@ -16,4 +16,10 @@ pub const PRESET_PARAMETER: &[u8] = b"preset_parameter";
 #[constant]
 pub const POSITION: &[u8] = b"position";
 
+#[constant]
+pub const WHITELISTED_WALLET: &[u8] = b"whitelisted_wallet";
+
+#[constant]
+pub const FEE_TIER: &[u8] = b"fee_tier";
+
 pub const ILM_BASE_KEY: Pubkey = pubkey!("MFGQxwAmB91SwuYX36okv2Qmdc9aMuHTwWGUrp4AtB1");
@@ -2,13 +2,11 @@ mod pool;
 mod position;
 mod deposit;
 mod withdraw;
-mod bin_array_manager;
 
 pub use pool::*;
 pub use position::*;
 pub use deposit::*;
 pub use withdraw::*;
-pub use bin_array_manager::*;
 
 use crate::state::{AllPosition, SinglePosition};
 use crate::wallet::WalletManager;
@@ -1,8 +1,8 @@
-use crate::bin_array_manager::BinArrayManager;
 use crate::pair_config::PairConfig;
 use anchor_lang::prelude::Pubkey;
 use anchor_spl::token::Mint;
 use anyhow::*;
+use commons::position::position_value;
 use lb_clmm::math::price_math::get_price_from_id;
 use lb_clmm::math::safe_math::SafeMath;
 use lb_clmm::math::u64x64_math::to_decimal;
@@ -11,7 +11,6 @@ use lb_clmm::state::bin::Bin;
 use lb_clmm::state::bin::BinArray;
 use lb_clmm::state::lb_pair::LbPair;
 use lb_clmm::state::position::PositionV2;
-use lb_clmm::utils::pda;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -77,52 +76,30 @@ impl SinglePosition {
         if self.positions.len() == 0 {
             return Ok(PositionRaw::default());
         }
+        let price = get_price_from_id(self.lb_pair_state.active_id, self.lb_pair_state.bin_step)?;
         let mut amount_x = 0u64;
         let mut amount_y = 0u64;
         let mut fee_x = 0u64;
         let mut fee_y = 0u64;
         for position in self.positions.iter() {
-            let lower_bin_array_idx = BinArray::bin_id_to_bin_array_index(position.lower_bin_id)?;
-            let upper_bin_array_idx = lower_bin_array_idx.checked_add(1).context("MathOverflow")?;
-            let mut bin_arrays = vec![];
-            for i in lower_bin_array_idx..=upper_bin_array_idx {
-                let (bin_array_pk, _bump) = pda::derive_bin_array_pda(self.lb_pair, i.into());
-
-                let bin_array_state = self
-                    .bin_arrays
-                    .get(&bin_array_pk)
-                    .ok_or(Error::msg("Cannot get binarray"))?;
-                bin_arrays.push(*bin_array_state);
-            }
-            let bin_array_manager = BinArrayManager {
-                bin_arrays: &bin_arrays,
-            };
-
-            for (i, &share) in position.liquidity_shares.iter().enumerate() {
-                if share == 0 {
-                    continue;
-                }
-
-                let bin_id = position.from_idx_to_bin_id(i)?;
-                let bin = bin_array_manager.get_bin(bin_id)?;
-                let (bin_amount_x, bin_amount_y) = bin.calculate_out_amount(share)?;
-                amount_x = amount_x
-                    .safe_add(bin_amount_x)
-                    .map_err(|_| Error::msg("Math is overflow"))?;
-                amount_y = amount_y
-                    .safe_add(bin_amount_y)
-                    .map_err(|_| Error::msg("Math is overflow"))?;
-
-                // println!("bin: {bin_id} amount_x: {amount_x} amount_y: {amount_y}");
-            }
-
-            let (fee_x_pending, fee_y_pending) =
-                bin_array_manager.get_total_fee_pending(position)?;
+            let value = position_value(
+                &self.lb_pair_state,
+                position,
+                &self.bin_arrays,
+                price,
+                self.last_update_timestamp,
+            )?;
+            amount_x = amount_x
+                .safe_add(value.amount_x)
+                .map_err(|_| Error::msg("Math is overflow"))?;
+            amount_y = amount_y
+                .safe_add(value.amount_y)
+                .map_err(|_| Error::msg("Math is overflow"))?;
             fee_x = fee_x
-                .safe_add(fee_x_pending)
+                .safe_add(value.fee_x)
                 .map_err(|_| Error::msg("Math is overflow"))?;
             fee_y = fee_y
-                .safe_add(fee_y_pending)
+                .safe_add(value.fee_y)
                 .map_err(|_| Error::msg("Math is overflow"))?;
         }
 